@@ -0,0 +1,167 @@
+#ifndef BLUE_GACHA_H
+#define BLUE_GACHA_H
+
+/* Generated by cbindgen from blue-gacha-ffi/src/lib.rs with `cbindgen --output include/blue_gacha.h`. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Reads names in English
+ */
+#define BG_LANGUAGE_ENGLISH 0
+
+/**
+ * Reads names in Japanese
+ */
+#define BG_LANGUAGE_JAPANESE 1
+
+/**
+ * A Banner, along with the pity progress of the pulls made on it
+ */
+typedef struct BgBanner BgBanner;
+
+/**
+ * A Collection of every Student known to the Gacha Simulator
+ */
+typedef struct BgDatabase BgDatabase;
+
+/**
+ * A seeded source of randomness
+ */
+typedef struct BgRng BgRng;
+
+/**
+ * A Student pulled from a Banner
+ *
+ * Pointers to a BgStudent point to the Student itself, so this is never
+ * constructed.
+ */
+typedef struct BgStudent BgStudent;
+
+/**
+ * Reads the message of the last error which happened on this thread
+ *
+ * # Safety
+ * `buf` must be NULL or point to at least `len` writable bytes.
+ */
+size_t bg_last_error(char *buf, size_t len);
+
+/**
+ * Loads a Student database from a JSON array of Students
+ *
+ * Returns `NULL` if the JSON is invalid.
+ *
+ * # Safety
+ * `json` must be a NUL-terminated string.
+ */
+struct BgDatabase *bg_database_from_json(const char *json);
+
+/**
+ * Frees a Student database. Banners built from it stay valid.
+ *
+ * # Safety
+ * `db` must be NULL or have been returned by [`bg_database_from_json`], and
+ * must not be used afterwards.
+ */
+void bg_database_free(struct BgDatabase *db);
+
+/**
+ * Builds a Banner from a JSON banner definition, looking its Students up in
+ * a database
+ *
+ * Returns `NULL` if the JSON is invalid, or refers to unknown Students.
+ *
+ * # Safety
+ * `db` must have been returned by [`bg_database_from_json`], and `json` must
+ * be a NUL-terminated string.
+ */
+struct BgBanner *bg_banner_from_json(const struct BgDatabase *db, const char *json);
+
+/**
+ * Frees a Banner, along with every Student pulled from it
+ *
+ * # Safety
+ * `banner` must be NULL or have been returned by [`bg_banner_from_json`],
+ * and must not be used afterwards.
+ */
+void bg_banner_free(struct BgBanner *banner);
+
+/**
+ * Reads the name of a Banner, falling back to Japanese if there is no translation
+ *
+ * Returns `SIZE_MAX` if `language` isn't one of the `BG_LANGUAGE_*` constants.
+ *
+ * # Safety
+ * `banner` must be a valid Banner, and `buf` must be NULL or point to at
+ * least `len` writable bytes.
+ */
+size_t bg_banner_name(const struct BgBanner *banner, uint32_t language, char *buf, size_t len);
+
+/**
+ * Returns the version of the procedure which turns a seed into pulls, see
+ * [`blue_gacha::rng::SAMPLING_VERSION`]
+ */
+uint32_t bg_sampling_version(void);
+
+/**
+ * Creates a source of randomness. The same seed always gives the same pulls
+ * for a given sampling version.
+ */
+struct BgRng *bg_rng_new(uint64_t seed);
+
+/**
+ * Frees a source of randomness
+ *
+ * # Safety
+ * `rng` must be NULL or have been returned by [`bg_rng_new`], and must not be
+ * used afterwards.
+ */
+void bg_rng_free(struct BgRng *rng);
+
+/**
+ * Performs a single pull
+ *
+ * Returns `NULL` if the pull failed.
+ *
+ * # Safety
+ * `banner` and `rng` must be valid, and must not be used by another thread
+ * at the same time.
+ */
+const struct BgStudent *bg_banner_roll(struct BgBanner *banner, struct BgRng *rng);
+
+/**
+ * Performs a 10-pull, writing the 10 Students to `out`
+ *
+ * Returns `false` if the Banner's purchase limit has been reached, or if
+ * the pull failed.
+ *
+ * # Safety
+ * `banner` and `rng` must be valid, and must not be used by another thread
+ * at the same time. `out` must point to an array of at least 10 Students.
+ */
+bool bg_banner_roll10(struct BgBanner *banner, struct BgRng *rng, const struct BgStudent **out);
+
+/**
+ * Reads the name of a Student, falling back to Japanese if there is no translation
+ *
+ * Returns `SIZE_MAX` if `language` isn't one of the `BG_LANGUAGE_*` constants.
+ *
+ * # Safety
+ * `student` must have been returned by a pull on a Banner which hasn't been
+ * freed, and `buf` must be NULL or point to at least `len` writable bytes.
+ */
+size_t bg_student_name(const struct BgStudent *student, uint32_t language, char *buf, size_t len);
+
+/**
+ * Returns the Rarity of a Student, where 1 is the most common
+ *
+ * # Safety
+ * `student` must have been returned by a pull on a Banner which hasn't been freed.
+ */
+uint8_t bg_student_rarity(const struct BgStudent *student);
+
+#endif /* BLUE_GACHA_H */
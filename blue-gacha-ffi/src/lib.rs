@@ -0,0 +1,351 @@
+//! A C API for embedding the blue-gacha engine, built as a shared library
+//! and declared in `include/blue_gacha.h`
+//!
+//! Every function is prefixed with `bg_`. Objects created by a `bg_*_new` or
+//! `bg_*_from_json` function must be released with the matching `bg_*_free`
+//! function. Students returned by a pull are borrowed from their Banner, and
+//! stay valid until the Banner is freed.
+//!
+//! Functions which can fail return `NULL` or `false`, and the reason can be
+//! read with [`bg_last_error`]. Panics are caught rather than unwound into C,
+//! and are reported the same way.
+//!
+//! Strings are read into a caller-provided buffer, like `snprintf`: at most
+//! `len - 1` bytes are written followed by a NUL, and the full length of the
+//! string (without the NUL) is returned. Names are read in one of the
+//! `BG_LANGUAGE_*` languages, and any other language is an error.
+use blue_gacha::banner::Banner;
+use blue_gacha::database::StudentDatabase;
+use blue_gacha::definition::BannerDefinition;
+use blue_gacha::error::Error;
+use blue_gacha::i18n::{I18nString, Language};
+use blue_gacha::pity::PityState;
+use blue_gacha::rng::{self, SeededRng};
+use blue_gacha::student::Student;
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Reads names in English
+pub const BG_LANGUAGE_ENGLISH: u32 = 0;
+/// Reads names in Japanese
+pub const BG_LANGUAGE_JAPANESE: u32 = 1;
+
+/// A Collection of every Student known to the Gacha Simulator
+pub struct BgDatabase {
+    db: StudentDatabase,
+}
+
+/// A Student pulled from a Banner
+///
+/// Pointers to a BgStudent point to the Student itself, so this is never
+/// constructed.
+pub struct BgStudent {
+    _private: [u8; 0],
+}
+
+/// A Banner, along with the pity progress of the pulls made on it
+pub struct BgBanner {
+    banner: Banner,
+    pity: PityState,
+}
+
+/// A seeded source of randomness
+pub struct BgRng {
+    rng: SeededRng,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+fn set_error(message: impl ToString) {
+    LAST_ERROR.with(|error| *error.borrow_mut() = message.to_string());
+}
+
+/// Runs `f`, returning `default` and setting the last error if it panics
+fn catch_panic<T>(default: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        set_error(format!("panicked: {}", message));
+        default
+    })
+}
+
+/// Reads a NUL-terminated UTF-8 string
+unsafe fn read_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        set_error("unexpected NULL string");
+        return None;
+    }
+
+    match CStr::from_ptr(ptr).to_str() {
+        Ok(value) => Some(value),
+        Err(err) => {
+            set_error(err);
+            None
+        }
+    }
+}
+
+/// Copies a string into a caller-provided buffer, see the module documentation
+unsafe fn write_str(value: &str, buf: *mut c_char, len: usize) -> usize {
+    if !buf.is_null() && len > 0 {
+        // Truncated strings are cut between characters, so they stay valid UTF-8
+        let mut written = value.len().min(len - 1);
+        while !value.is_char_boundary(written) {
+            written -= 1;
+        }
+        std::ptr::copy_nonoverlapping(value.as_ptr(), buf as *mut u8, written);
+        *buf.add(written) = 0;
+    }
+
+    value.len()
+}
+
+/// Copies a name into a caller-provided buffer in one of the `BG_LANGUAGE_*`
+/// languages, falling back to Japanese if there is no translation
+///
+/// Returns `usize::MAX` and writes an empty string if the language is unknown.
+unsafe fn write_name(name: &I18nString, language: u32, buf: *mut c_char, len: usize) -> usize {
+    let language = match language {
+        BG_LANGUAGE_ENGLISH => Language::English,
+        BG_LANGUAGE_JAPANESE => Language::Japanese,
+        _ => {
+            set_error(format!("unknown language {}", language));
+            write_str("", buf, len);
+            return usize::MAX;
+        }
+    };
+
+    let name = name
+        .translation(language)
+        .or_else(|| name.translation(Language::Japanese))
+        .unwrap_or_default();
+    write_str(name, buf, len)
+}
+
+/// Reads the message of the last error which happened on this thread
+///
+/// # Safety
+/// `buf` must be NULL or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bg_last_error(buf: *mut c_char, len: usize) -> usize {
+    LAST_ERROR.with(|error| write_str(&error.borrow(), buf, len))
+}
+
+/// Loads a Student database from a JSON array of Students
+///
+/// Returns `NULL` if the JSON is invalid.
+///
+/// # Safety
+/// `json` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bg_database_from_json(json: *const c_char) -> *mut BgDatabase {
+    let json = match read_str(json) {
+        Some(json) => json,
+        None => return std::ptr::null_mut(),
+    };
+
+    match StudentDatabase::from_json(json) {
+        Ok(db) => Box::into_raw(Box::new(BgDatabase { db })),
+        Err(err) => {
+            set_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Frees a Student database. Banners built from it stay valid.
+///
+/// # Safety
+/// `db` must be NULL or have been returned by [`bg_database_from_json`], and
+/// must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bg_database_free(db: *mut BgDatabase) {
+    if !db.is_null() {
+        drop(Box::from_raw(db));
+    }
+}
+
+/// Builds a Banner from a JSON banner definition, looking its Students up in
+/// a database
+///
+/// Returns `NULL` if the JSON is invalid, or refers to unknown Students.
+///
+/// # Safety
+/// `db` must have been returned by [`bg_database_from_json`], and `json` must
+/// be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bg_banner_from_json(
+    db: *const BgDatabase,
+    json: *const c_char,
+) -> *mut BgBanner {
+    let db = match db.as_ref() {
+        Some(db) => &db.db,
+        None => {
+            set_error("unexpected NULL database");
+            return std::ptr::null_mut();
+        }
+    };
+    let json = match read_str(json) {
+        Some(json) => json,
+        None => return std::ptr::null_mut(),
+    };
+
+    catch_panic(std::ptr::null_mut(), || {
+        let banner = serde_json::from_str::<BannerDefinition>(json)
+            .map_err(Error::from)
+            .and_then(|definition| definition.build(db));
+
+        match banner {
+            Ok(banner) => Box::into_raw(Box::new(BgBanner {
+                banner,
+                pity: PityState::default(),
+            })),
+            Err(err) => {
+                set_error(err);
+                std::ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Frees a Banner, along with every Student pulled from it
+///
+/// # Safety
+/// `banner` must be NULL or have been returned by [`bg_banner_from_json`],
+/// and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bg_banner_free(banner: *mut BgBanner) {
+    if !banner.is_null() {
+        drop(Box::from_raw(banner));
+    }
+}
+
+/// Reads the name of a Banner, falling back to Japanese if there is no translation
+///
+/// Returns `SIZE_MAX` if `language` isn't one of the `BG_LANGUAGE_*` constants.
+///
+/// # Safety
+/// `banner` must be a valid Banner, and `buf` must be NULL or point to at
+/// least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bg_banner_name(
+    banner: *const BgBanner,
+    language: u32,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    write_name(&(*banner).banner.name, language, buf, len)
+}
+
+/// Returns the version of the procedure which turns a seed into pulls, see
+/// [`blue_gacha::rng::SAMPLING_VERSION`]
+#[no_mangle]
+pub extern "C" fn bg_sampling_version() -> u32 {
+    rng::SAMPLING_VERSION
+}
+
+/// Creates a source of randomness. The same seed always gives the same pulls
+/// for a given sampling version.
+#[no_mangle]
+pub extern "C" fn bg_rng_new(seed: u64) -> *mut BgRng {
+    Box::into_raw(Box::new(BgRng {
+        rng: rng::seeded(seed),
+    }))
+}
+
+/// Frees a source of randomness
+///
+/// # Safety
+/// `rng` must be NULL or have been returned by [`bg_rng_new`], and must not be
+/// used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bg_rng_free(rng: *mut BgRng) {
+    if !rng.is_null() {
+        drop(Box::from_raw(rng));
+    }
+}
+
+/// Performs a single pull
+///
+/// Returns `NULL` if the pull failed.
+///
+/// # Safety
+/// `banner` and `rng` must be valid, and must not be used by another thread
+/// at the same time.
+#[no_mangle]
+pub unsafe extern "C" fn bg_banner_roll(
+    banner: *mut BgBanner,
+    rng: *mut BgRng,
+) -> *const BgStudent {
+    let banner = &mut *banner;
+    let rng = &mut (*rng).rng;
+    catch_panic(std::ptr::null(), || {
+        banner.banner.roll_ref_with_rng(&mut banner.pity, rng) as *const Student as *const BgStudent
+    })
+}
+
+/// Performs a 10-pull, writing the 10 Students to `out`
+///
+/// Returns `false` if the Banner's purchase limit has been reached, or if
+/// the pull failed.
+///
+/// # Safety
+/// `banner` and `rng` must be valid, and must not be used by another thread
+/// at the same time. `out` must point to an array of at least 10 Students.
+#[no_mangle]
+pub unsafe extern "C" fn bg_banner_roll10(
+    banner: *mut BgBanner,
+    rng: *mut BgRng,
+    out: *mut *const BgStudent,
+) -> bool {
+    let banner = &mut *banner;
+    let rng = &mut (*rng).rng;
+    catch_panic(false, || {
+        match banner.banner.try_roll10_ref_with_rng(&mut banner.pity, rng) {
+            Some(students) => {
+                for (i, student) in students.iter().enumerate() {
+                    *out.add(i) = *student as *const Student as *const BgStudent;
+                }
+                true
+            }
+            None => {
+                set_error("the banner's purchase limit has been reached");
+                false
+            }
+        }
+    })
+}
+
+/// Reads the name of a Student, falling back to Japanese if there is no translation
+///
+/// Returns `SIZE_MAX` if `language` isn't one of the `BG_LANGUAGE_*` constants.
+///
+/// # Safety
+/// `student` must have been returned by a pull on a Banner which hasn't been
+/// freed, and `buf` must be NULL or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bg_student_name(
+    student: *const BgStudent,
+    language: u32,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    write_name(&(*(student as *const Student)).name, language, buf, len)
+}
+
+/// Returns the Rarity of a Student, where 1 is the most common
+///
+/// # Safety
+/// `student` must have been returned by a pull on a Banner which hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bg_student_rarity(student: *const BgStudent) -> u8 {
+    (*(student as *const Student)).rarity.0
+}
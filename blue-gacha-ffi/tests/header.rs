@@ -0,0 +1,23 @@
+//! The committed C header must match what cbindgen generates from the source
+//!
+//! If this fails, regenerate it from the `blue-gacha-ffi` directory with
+//! `cbindgen --output include/blue_gacha.h` and commit the result.
+use std::path::Path;
+
+#[test]
+fn header_is_up_to_date() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let mut generated = Vec::new();
+    cbindgen::generate(crate_dir)
+        .expect("failed to generate the C header")
+        .write(&mut generated);
+
+    let committed = std::fs::read(Path::new(crate_dir).join("include/blue_gacha.h"))
+        .expect("failed to read include/blue_gacha.h");
+
+    assert!(
+        generated == committed,
+        "include/blue_gacha.h is out of date, regenerate it with cbindgen:\n{}",
+        String::from_utf8_lossy(&generated)
+    );
+}
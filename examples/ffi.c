@@ -0,0 +1,65 @@
+/*
+ * Performs a seeded 10-pull through the C API.
+ *
+ *     cargo build -p blue-gacha-ffi
+ *     cc -Iblue-gacha-ffi/include examples/ffi.c -Ltarget/debug -lblue_gacha_ffi -o ffi
+ *     LD_LIBRARY_PATH=target/debug ./ffi
+ */
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "blue_gacha.h"
+
+static char *read_file(const char *path) {
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        return NULL;
+    }
+
+    fseek(file, 0, SEEK_END);
+    long len = ftell(file);
+    rewind(file);
+
+    char *contents = malloc(len + 1);
+    fread(contents, 1, len, file);
+    contents[len] = '\0';
+    fclose(file);
+    return contents;
+}
+
+int main(void) {
+    char name[128];
+
+    char *students = read_file("./examples/students.json");
+    BgDatabase *db = bg_database_from_json(students);
+    free(students);
+    if (!db) {
+        bg_last_error(name, sizeof name);
+        fprintf(stderr, "%s\n", name);
+        return 1;
+    }
+
+    BgBanner *banner = bg_banner_from_json(
+        db, "{ \"name\": { \"translations\": { \"jpn\": \"ホシノ ピックアップ募集\" } },"
+            "  \"priority\": [{ \"student\": \"ホシノ\", \"rate\": 0.7 }] }");
+    bg_database_free(db);
+    if (!banner) {
+        bg_last_error(name, sizeof name);
+        fprintf(stderr, "%s\n", name);
+        return 1;
+    }
+
+    BgRng *rng = bg_rng_new(7);
+    const BgStudent *students_pulled[10];
+
+    if (bg_banner_roll10(banner, rng, students_pulled)) {
+        for (int i = 0; i < 10; i++) {
+            bg_student_name(students_pulled[i], BG_LANGUAGE_ENGLISH, name, sizeof name);
+            printf("%s\t%u-star\n", name, bg_student_rarity(students_pulled[i]));
+        }
+    }
+
+    bg_rng_free(rng);
+    bg_banner_free(banner);
+    return 0;
+}
@@ -0,0 +1,40 @@
+use blue_gacha::database::StudentDatabase;
+use blue_gacha::i18n::{Language, Localize};
+use blue_gacha::schedule::{BannerSchedule, Server};
+use chrono::NaiveDate;
+use std::{fs::File, io::Read};
+
+const LANGUAGE: Language = Language::English;
+
+fn main() {
+    let mut students_str = String::new();
+    let mut students = File::open("./examples/students.json").unwrap();
+    students.read_to_string(&mut students_str).unwrap();
+
+    let mut schedule_str = String::new();
+    let mut schedule = File::open("./examples/schedule.json").unwrap();
+    schedule.read_to_string(&mut schedule_str).unwrap();
+
+    let db = StudentDatabase::from_json_strict(&students_str).unwrap();
+    let schedule = BannerSchedule::from_json(&schedule_str).unwrap();
+
+    let date = NaiveDate::from_ymd_opt(2021, 2, 18).unwrap();
+
+    println!("Banners live on {}:", date);
+    for scheduled in schedule.live_on(date, Server::Japan) {
+        let banner = scheduled.banner.build(&db).unwrap();
+        println!(
+            "  {} (until {})",
+            banner.display_in(LANGUAGE),
+            scheduled.end
+        );
+    }
+
+    let pool = schedule.combined_pool(&db, date, Server::Japan).unwrap();
+    println!("{} students can be pulled on {}", pool.len(), date);
+
+    match schedule.next_appearance(&db, &"ホシノ".into(), date, Server::Global) {
+        Some(scheduled) => println!("Hoshino next appears on Global on {}", scheduled.start),
+        None => println!("Hoshino has no upcoming Global banner"),
+    }
+}
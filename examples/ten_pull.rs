@@ -1,13 +1,15 @@
 use blue_gacha::banner::BannerBuilder;
+use blue_gacha::database::StudentDatabase;
 use blue_gacha::gacha::{GachaBuilder, Recruitment};
-use blue_gacha::i18n::Language;
-use blue_gacha::student::Student;
+use blue_gacha::i18n::{Language, Localize};
 use std::{fs::File, io::Read};
 
 const THREE_STAR_RATE: f32 = 2.5;
 const TWO_STAR_RATE: f32 = 18.5;
 const ONE_STAR_RATE: f32 = 79.0;
 
+const LANGUAGE: Language = Language::English;
+
 const KARIN_RATE: f32 = 0.7;
 const MUTSUKI_RATE: f32 = 0.3;
 
@@ -22,21 +24,26 @@ fn main() {
     let mut students = File::open("./examples/students.json").unwrap();
     students.read_to_string(&mut students_str).unwrap();
 
-    let students: Vec<Student> = serde_json::from_str(&students_str).unwrap();
+    // Strict loading guarantees that every student has an English name
+    let db = StudentDatabase::from_json_strict(&students_str).unwrap();
 
-    let karin = find_student(&students, "カリン")
+    let karin = db
+        .find("カリン")
         .expect("カリン is not present in ./examples/students.json")
+        .clone()
         .into_priority_student(KARIN_RATE);
 
-    let mutsuki = find_student(&students, "ムツキ")
+    let mutsuki = db
+        .find("ムツキ")
         .expect("ムツキ is not present in ./examples/students.json")
+        .clone()
         .into_priority_student(MUTSUKI_RATE);
 
     let sparkable = vec![karin.student().clone()];
     let priority = vec![karin, mutsuki];
 
     let gacha = GachaBuilder::new(ONE_STAR_RATE, TWO_STAR_RATE, THREE_STAR_RATE)
-        .with_pool(students)
+        .with_pool(db.students().to_vec())
         .with_priority(priority)
         .finish()
         .unwrap();
@@ -51,19 +58,12 @@ fn main() {
 
     let students = banner.roll10();
 
-    println!("{} 10-pull: \n", banner.name);
+    println!("{} 10-pull: \n", banner.display_in(LANGUAGE));
     for student in students.iter() {
         println!(
             "{} {}",
-            student.name.get(Language::English).unwrap(),
-            student.rarity
+            student.name.display_in(LANGUAGE),
+            student.rarity.display_in(LANGUAGE)
         );
     }
 }
-
-pub fn find_student(students: &[Student], jpn_name: &str) -> Option<Student> {
-    students
-        .iter()
-        .find(|student| student.name == jpn_name)
-        .map(|student| student.clone())
-}
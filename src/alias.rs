@@ -0,0 +1,95 @@
+use rand::Rng;
+
+/// A Walker alias table, which picks from a set of weighted outcomes in
+/// constant time
+///
+/// Building the table takes linear time, so it is meant to be built once and
+/// sampled many times. Weights are integers, which keeps sampling exact:
+/// outcome `i` is picked with a probability of exactly `weights[i] / total`.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::alias::AliasTable;
+/// let table = AliasTable::new(&[790, 185, 25]).unwrap();
+/// let mut rng = rand::thread_rng();
+///
+/// assert!(table.sample(&mut rng) < 3);
+/// assert_eq!(table.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AliasTable {
+    /// The chance (in terms of `total`) of keeping a column instead of
+    /// taking its alias
+    threshold: Vec<u64>,
+    alias: Vec<usize>,
+    total: u64,
+}
+
+impl AliasTable {
+    /// Builds an alias table using Vose's method
+    ///
+    /// Will return `None` if there are no weights, or if every weight is 0.
+    ///
+    /// # Arguments
+    /// * `weights` - The weight of every outcome
+    pub fn new(weights: &[usize]) -> Option<Self> {
+        let n = weights.len() as u64;
+        let total: u64 = weights.iter().map(|&weight| weight as u64).sum();
+
+        if total == 0 {
+            return None;
+        }
+
+        // Every column holds exactly `total` once scaled by `n`
+        let mut scaled: Vec<u64> = weights.iter().map(|&weight| weight as u64 * n).collect();
+        let mut alias: Vec<usize> = (0..weights.len()).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..weights.len()).partition(|&i| scaled[i] < total);
+
+        while let (Some(less), Some(&more)) = (small.pop(), large.last()) {
+            alias[less] = more;
+            scaled[more] -= total - scaled[less];
+
+            if scaled[more] < total {
+                large.pop();
+                small.push(more);
+            }
+        }
+
+        // Whatever is left over is full, barring rounding, which integers don't have
+        for i in large.into_iter().chain(small) {
+            scaled[i] = total;
+        }
+
+        Some(Self {
+            threshold: scaled,
+            alias,
+            total,
+        })
+    }
+
+    /// Returns the number of outcomes
+    pub fn len(&self) -> usize {
+        self.alias.len()
+    }
+
+    /// Returns true if there are no outcomes, which never happens for a
+    /// successfully built table
+    pub fn is_empty(&self) -> bool {
+        self.alias.is_empty()
+    }
+
+    /// Picks the index of an outcome
+    ///
+    /// # Arguments
+    /// * `rng` - The source of randomness
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let column = rng.gen_range(0..self.alias.len());
+
+        if rng.gen_range(0..self.total) < self.threshold[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
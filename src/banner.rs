@@ -1,19 +1,55 @@
-use crate::gacha::{Gacha, Rarity, Recruitment};
-use crate::i18n::{I18nString, Language};
+use crate::error::Error;
+use crate::gacha::{Gacha, Item, Rarity, Recruitment};
+use crate::i18n::{I18nString, Language, Localize};
+use crate::pity::{Pity, PityState};
+use crate::rules::{BlueArchive, RuleSet};
+use crate::sampler::Sampler;
+use crate::simulation::Simulation;
 use crate::student::{PriorityStudent, Student};
-use rand::distributions::{Distribution, WeightedIndex};
-use rand::Rng;
-use std::convert::{TryFrom, TryInto};
+use rand::RngCore;
+use std::cell::Cell;
+use std::sync::Arc;
+
+/// The amount of Pyroxene a single pull costs
+pub const PULL_COST: u32 = 120;
+
+/// The amount of pulls on a single Banner needed to spark (exchange
+/// recruitment points for) one of its sparkable Students
+pub const SPARK_THRESHOLD: u32 = 200;
+
+/// The amount of Pyroxene a 10-pull on the Beginner Recruitment costs
+pub const BEGINNER_TEN_PULL_COST: u32 = 600;
+
+/// The amount of times the Beginner Recruitment's 10-pull can be purchased
+pub const BEGINNER_PURCHASE_LIMIT: u32 = 5;
 
 /// Used to Construct a Banner
-#[derive(Debug, Default)]
-pub struct BannerBuilder {
+#[derive(Debug)]
+pub struct BannerBuilder<T: Item = Student> {
     name: I18nString,
-    gacha: Option<Gacha>,
-    sparkable: Option<Vec<Student>>,
+    gacha: Option<Gacha<T>>,
+    sparkable: Option<Vec<T>>,
+    ten_pull_cost: Option<u32>,
+    purchase_limit: Option<u32>,
+    final_guarantee: Option<Rarity>,
+    rules: Option<Arc<dyn RuleSet<T>>>,
 }
 
-impl BannerBuilder {
+impl<T: Item> Default for BannerBuilder<T> {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            gacha: None,
+            sparkable: None,
+            ten_pull_cost: None,
+            purchase_limit: None,
+            final_guarantee: None,
+            rules: None,
+        }
+    }
+}
+
+impl<T: Item> BannerBuilder<T> {
     /// Creates a new instance of a BannerBuilder
     ///
     /// # Arguments
@@ -22,7 +58,7 @@ impl BannerBuilder {
     /// # Examples
     /// ```
     /// # use blue_gacha::banner::BannerBuilder;
-    /// let banner_builder = BannerBuilder::new("ピックアップ募集");
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("ピックアップ募集");
     /// ```
     pub fn new(jpn_name: &str) -> Self {
         Self {
@@ -41,7 +77,7 @@ impl BannerBuilder {
     /// ```
     /// # use blue_gacha::banner::BannerBuilder;
     /// # use blue_gacha::i18n::Language;
-    /// let banner_builder = BannerBuilder::new("ピックアップ募集")
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("ピックアップ募集")
     ///     .with_name_translation(Language::English, "Focus Recruitment");
     /// ```
     pub fn with_name_translation(mut self, language: Language, name: &str) -> Self {
@@ -62,10 +98,10 @@ impl BannerBuilder {
     ///     .with_pool(Vec::new())
     ///     .finish().unwrap();
     ///
-    /// let banner_builder = BannerBuilder::new("ピックアップ募集")
-    ///     .with_gacha(&gacha);
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("ピックアップ募集")
+    ///     .with_gacha(gacha);
     /// ```
-    pub fn with_gacha(self, gacha: Gacha) -> Self {
+    pub fn with_gacha(self, gacha: Gacha<T>) -> Self {
         Self {
             gacha: Some(gacha),
             ..self
@@ -82,156 +118,667 @@ impl BannerBuilder {
     /// # use blue_gacha::student::Student;
     /// # use blue_gacha::banner::BannerBuilder;
     /// let students = Vec::new();
-    /// let banner_builder = BannerBuilder::new("ピックアップ募集")
-    ///     .with_sparkable_students(&students);
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("ピックアップ募集")
+    ///     .with_sparkable_students(students);
     /// ```
-    pub fn with_sparkable_students(self, students: Vec<Student>) -> Self {
+    pub fn with_sparkable_students(self, students: Vec<T>) -> Self {
         Self {
             sparkable: Some(students.to_vec()),
             ..self
         }
     }
 
-    pub fn finish(self) -> Option<Banner> {
+    /// Overrides the amount of Pyroxene a 10-pull costs
+    ///
+    /// # Arguments
+    /// * `cost` - The amount of Pyroxene a 10-pull costs
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("初心者募集")
+    ///     .with_ten_pull_cost(600);
+    /// ```
+    pub fn with_ten_pull_cost(self, cost: u32) -> Self {
+        Self {
+            ten_pull_cost: Some(cost),
+            ..self
+        }
+    }
+
+    /// Limits the amount of 10-pulls which can be purchased on this Banner
+    ///
+    /// # Arguments
+    /// * `limit` - The amount of 10-pulls which can be purchased
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("初心者募集")
+    ///     .with_purchase_limit(5);
+    /// ```
+    pub fn with_purchase_limit(self, limit: u32) -> Self {
+        Self {
+            purchase_limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Guarantees a Student of this Rarity or higher in the last 10-pull a
+    /// purchase-limited Banner allows
+    ///
+    /// # Arguments
+    /// * `rarity` - The guaranteed Rarity
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::Rarity;
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("初心者募集")
+    ///     .with_purchase_limit(5)
+    ///     .with_final_purchase_guarantee(Rarity::Three);
+    /// ```
+    pub fn with_final_purchase_guarantee(self, rarity: Rarity) -> Self {
+        Self {
+            final_guarantee: Some(rarity),
+            ..self
+        }
+    }
+
+    /// Replaces the Blue Archive rules this Banner uses by default
+    ///
+    /// # Arguments
+    /// * `rules` - The rules of the Banner
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::rules::BlueArchive;
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("ピックアップ募集")
+    ///     .with_rules(BlueArchive);
+    /// ```
+    pub fn with_rules<R: RuleSet<T> + 'static>(self, rules: R) -> Self {
+        Self {
+            rules: Some(Arc::new(rules)),
+            ..self
+        }
+    }
+
+    /// Uses pity rules, which Blue Archive's Banners don't have, instead of
+    /// the Blue Archive rules
+    ///
+    /// # Arguments
+    /// * `pity` - The pity rules of the Banner
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::pity::Pity;
+    /// let banner_builder: BannerBuilder = BannerBuilder::new("ピックアップ募集")
+    ///     .with_pity(Pity::new().with_hard_pity(90).with_fifty_fifty());
+    /// ```
+    pub fn with_pity(self, pity: Pity) -> Self {
+        self.with_rules(pity)
+    }
+
+    /// Creates a BannerBuilder for the Beginner Recruitment, which has a
+    /// discounted 10-pull, a purchase limit, and guarantees a 3★ Student in
+    /// the final purchase
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::{BannerBuilder, BEGINNER_PURCHASE_LIMIT};
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity, Recruitment};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::beginner().with_gacha(gacha).finish().unwrap();
+    ///
+    /// for _ in 1..BEGINNER_PURCHASE_LIMIT {
+    ///     banner.roll10();
+    /// }
+    /// let students = banner.roll10();
+    ///
+    /// assert!(students.iter().any(|student| student.rarity == Rarity::Three));
+    /// assert_eq!(banner.purchases_remaining(), Some(0));
+    /// assert!(banner.try_roll10().is_none());
+    /// ```
+    pub fn beginner() -> Self {
+        BannerBuilder::new("初心者募集")
+            .with_name_translation(Language::English, "Beginner Recruitment")
+            .with_ten_pull_cost(BEGINNER_TEN_PULL_COST)
+            .with_purchase_limit(BEGINNER_PURCHASE_LIMIT)
+            .with_final_purchase_guarantee(Rarity::Three)
+    }
+
+    /// Consumes a BannerBuilder and returns a Banner
+    ///
+    /// Will return `None` if the Gacha hasn't been set, if some of its pulls
+    /// would have no Student to give (see [`Gacha::validate`]), or if the
+    /// final purchase guarantees a Rarity which no Student in the pool has.
+    pub fn finish(self) -> Option<Banner<T>> {
+        let gacha = self.gacha?;
+        gacha.validate().ok()?;
+
+        if let Some(rarity) = self.final_guarantee {
+            if !gacha.pool.iter().any(|student| student.rarity() == rarity) {
+                return None;
+            }
+        }
+
         Some(Banner {
             name: self.name,
-            gacha: self.gacha?,
+            sampler: Sampler::new(gacha),
             sparkable: self.sparkable,
+            ten_pull_cost: self.ten_pull_cost,
+            purchase_limit: self.purchase_limit,
+            final_guarantee: self.final_guarantee,
+            purchases: Cell::new(0),
+            rules: self.rules.unwrap_or_else(|| Arc::new(BlueArchive)),
+            pity_state: Default::default(),
         })
     }
 }
 
-#[derive(Debug, Clone)]
-enum StudentType<'a> {
-    One,   // One Star
-    Two,   // Two Stars
-    Three, // Three Stars
-    Priority(&'a PriorityStudent),
+/// Everything needed to pull on a Banner, which (unlike a Banner) can be
+/// shared between threads
+#[derive(Debug)]
+pub(crate) struct Roller<'a, T: Item> {
+    pub(crate) sampler: &'a Sampler<T>,
+    pub(crate) rules: &'a dyn RuleSet<T>,
+    purchase_limit: Option<u32>,
+    final_guarantee: Option<Rarity>,
 }
 
-impl<'a> From<Rarity> for StudentType<'a> {
-    fn from(rarity: Rarity) -> Self {
-        match rarity {
-            Rarity::One => Self::One,
-            Rarity::Two => Self::Two,
-            Rarity::Three => Self::Three,
-        }
+impl<'a, T: Item> Clone for Roller<'a, T> {
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
-impl<'a> TryFrom<StudentType<'a>> for Rarity {
-    type Error = &'static str;
+impl<'a, T: Item> Copy for Roller<'a, T> {}
 
-    fn try_from(value: StudentType) -> Result<Self, Self::Error> {
-        Rarity::try_from(&value)
+impl<'a, T: Item> Roller<'a, T> {
+    pub(crate) fn pull(self, state: &mut PityState, rng: &mut dyn RngCore) -> &'a T {
+        self.rules.pull(self.sampler, state, rng)
     }
-}
 
-impl<'a> TryFrom<&StudentType<'a>> for Rarity {
-    type Error = &'static str;
+    /// Performs a 10-pull, given how many 10-pulls have already been purchased
+    ///
+    /// Will return `None` if the purchase limit has been reached.
+    pub(crate) fn ten_pull(
+        self,
+        state: &mut PityState,
+        purchases: u32,
+        rng: &mut dyn RngCore,
+    ) -> Option<[&'a T; 10]> {
+        let remaining = self
+            .purchase_limit
+            .map(|limit| limit.saturating_sub(purchases));
+        if remaining == Some(0) {
+            return None;
+        }
 
-    fn try_from(value: &StudentType<'a>) -> Result<Self, Self::Error> {
-        Ok(match value {
-            StudentType::One => Self::One,
-            StudentType::Two => Self::Two,
-            StudentType::Three => Self::Three,
-            StudentType::Priority(_) => return Err("Can not convert from Priority to Rarity"),
-        })
+        // Fill students with 10 random students
+        let mut students: [&T; 10] =
+            std::array::from_fn(|_| self.rules.pull(self.sampler, state, rng));
+
+        self.rules
+            .apply_ten_pull_guarantee(self.sampler, &mut students, rng);
+
+        if let (Some(1), Some(rarity)) = (remaining, self.final_guarantee) {
+            self.guarantee_rarity(&mut students, rarity, rng);
+        }
+
+        Some(students)
+    }
+
+    /// Replaces the last Student of a 10-pull with a random Student of the given
+    /// Rarity, unless a Student of that Rarity or higher was already pulled
+    ///
+    /// Nothing is replaced if the pool has no Student of the given Rarity.
+    pub(crate) fn guarantee_rarity(
+        self,
+        students: &mut [&'a T; 10],
+        rarity: Rarity,
+        rng: &mut dyn RngCore,
+    ) {
+        if !students.iter().any(|student| student.rarity() >= rarity) {
+            // NOTE: This does not follow the Banner's increased rates
+            if let Some(student) = self.sampler.sample_rarity(rarity, rng) {
+                students[students.len() - 1] = student;
+            }
+        }
     }
 }
 
-pub struct Banner {
+#[derive(Debug, Clone)]
+pub struct Banner<T: Item = Student> {
     pub name: I18nString,
-    gacha: Gacha,
-    sparkable: Option<Vec<Student>>,
+    sampler: Sampler<T>,
+    sparkable: Option<Vec<T>>,
+    /// Overrides the RuleSet's 10-pull cost
+    ten_pull_cost: Option<u32>,
+    purchase_limit: Option<u32>,
+    final_guarantee: Option<Rarity>,
+    purchases: Cell<u32>,
+    rules: Arc<dyn RuleSet<T>>,
+    /// Pity progress of pulls made directly on this Banner, rather than through a Session
+    pity_state: Cell<PityState>,
 }
 
-impl Banner {
-    fn get_random_student(&self) -> Student {
-        let mut rng = rand::thread_rng();
-
-        let empty_vec = Vec::new();
-        let priority_students = self.gacha.priority.as_ref().unwrap_or(&empty_vec);
-        let mut rates = (
-            self.gacha.get_rate(Rarity::One),
-            self.gacha.get_rate(Rarity::Two),
-            self.gacha.get_rate(Rarity::Three),
-        );
-
-        for priority_student in priority_students {
-            match priority_student.student().rarity {
-                Rarity::One => rates.0 -= priority_student.rate,
-                Rarity::Two => rates.1 -= priority_student.rate,
-                Rarity::Three => rates.2 -= priority_student.rate,
-            };
-        }
+impl<T: Item> std::fmt::Display for Banner<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_localized(Language::Japanese, f)
+    }
+}
 
-        let mut items: Vec<(StudentType, usize)> = Vec::with_capacity(3 + priority_students.len());
-        items.push((StudentType::One, rates.0));
-        items.push((StudentType::Two, rates.1));
-        items.push((StudentType::Three, rates.2));
-
-        items.extend(
-            priority_students
-                .iter()
-                .map(|student| (StudentType::Priority(student), student.rate)),
-        );
-
-        let dist = WeightedIndex::new(items.iter().map(|item| item.1)).unwrap();
-        let student_pool = &self.gacha.pool;
-
-        match &items[dist.sample(&mut rng)] {
-            (StudentType::Priority(priority_student), _) => priority_student.student().clone(),
-            (student_type, _) => {
-                let students: Vec<&Student> = student_pool
-                    .iter()
-                    .filter(|student| student.rarity == student_type.try_into().unwrap())
-                    .filter(|student| {
-                        // Remove any Rate-Up Units
-                        // TODO: Determine whether this is the right way of implementing priority gacha
-                        !priority_students
-                            .iter()
-                            .any(|priority_student| student.name == priority_student.student().name)
-                    })
-                    .collect();
-                let index = rng.gen_range(0..students.len());
-                students[index].clone()
-            }
-        }
+impl<T: Item> Localize for Banner<T> {
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.name.fmt_localized(language, f)
     }
+}
 
-    fn get_random_student_of_rarity(&self, rarity: Rarity) -> Student {
-        // NOTE: This does not actually follow the rules of any given banner. Only get_random_student() does.
-        let students = &self.gacha.pool;
-        let two_star_students: Vec<&Student> = students
+impl<T: Item> Banner<T> {
+    /// Returns the Students which can be sparked (exchanged for recruitment
+    /// points) on this Banner, if there are any
+    pub fn sparkable(&self) -> Option<&[T]> {
+        self.sparkable.as_deref()
+    }
+
+    /// Returns true if the given Student can be sparked on this Banner
+    ///
+    /// # Arguments
+    /// * `student` - The Student to check
+    pub fn is_sparkable(&self, student: &T) -> bool {
+        self.sparkable()
+            .unwrap_or_default()
             .iter()
-            .filter(|student| student.rarity == rarity)
-            .collect();
+            .any(|sparkable| sparkable.id() == student.id())
+    }
 
-        let index = rand::thread_rng().gen_range(0..two_star_students.len());
-        two_star_students[index].clone()
+    /// Returns the Gacha this Banner pulls from
+    pub fn gacha(&self) -> &Gacha<T> {
+        self.sampler.gacha()
     }
-}
 
-impl Recruitment for Banner {
-    fn roll(&self) -> Student {
-        self.get_random_student()
+    /// Returns the rules this Banner follows
+    pub fn rules(&self) -> &dyn RuleSet<T> {
+        self.rules.as_ref()
     }
 
-    fn roll10(&self) -> [Student; 10] {
-        let mut students: [Student; 10] = vec![Default::default(); 10].try_into().unwrap();
+    /// Returns the amount of pulls needed to spark a Student, or `None` if
+    /// Students can't be sparked on this Banner
+    pub fn spark_threshold(&self) -> Option<u32> {
+        self.rules.spark_threshold()
+    }
 
-        // Fill students with 10 random students
-        for student in students.iter_mut() {
-            *student = self.get_random_student()
-        }
+    /// Returns the amount of Pyroxene a 10-pull costs
+    pub fn ten_pull_cost(&self) -> u32 {
+        self.ten_pull_cost
+            .unwrap_or_else(|| self.rules.ten_pull_cost())
+    }
 
-        let two_star_present = students.iter().any(|student| student.rarity == Rarity::Two);
+    /// Returns how many 10-pulls can be purchased in total, or `None` if there is no limit
+    pub fn purchase_limit(&self) -> Option<u32> {
+        self.purchase_limit
+    }
 
-        if !two_star_present && students[students.len() - 1].rarity != Rarity::Three {
-            students[students.len() - 1] = self.get_random_student_of_rarity(Rarity::Two);
-        }
+    /// Returns the Rarity the final purchasable 10-pull guarantees, if any
+    pub fn final_purchase_guarantee(&self) -> Option<Rarity> {
+        self.final_guarantee
+    }
 
+    /// Returns true if single pulls and recruitment tickets can be used on
+    /// this Banner
+    ///
+    /// Banners with a purchase limit, such as the Beginner Recruitment, only
+    /// sell whole 10-pulls.
+    pub fn accepts_tickets(&self) -> bool {
+        self.purchase_limit.is_none()
+    }
+
+    /// Returns how many more 10-pulls can be purchased, or `None` if there is no limit
+    pub fn purchases_remaining(&self) -> Option<u32> {
+        self.purchase_limit
+            .map(|limit| limit.saturating_sub(self.purchases.get()))
+    }
+
+    /// Performs a 10-pull, respecting the Banner's purchase limit
+    ///
+    /// Will return `None` if the purchase limit has been reached.
+    pub fn try_roll10(&self) -> Option<[T; 10]> {
+        self.try_roll10_ref().map(|students| students.map(T::clone))
+    }
+
+    /// Performs a single pull without cloning the Student, see [`Recruitment::roll`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![Student::new("ホシノ", Rarity::Three), Student::new("ハルカ", Rarity::One)];
+    /// let gacha = GachaBuilder::new(97.5, 0.0, 2.5).with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// let student: &Student = banner.roll_ref();
+    /// assert!(banner.gacha().pool.iter().any(|other| std::ptr::eq(other, student)));
+    /// ```
+    pub fn roll_ref(&self) -> &T {
+        let mut state = self.pity_state.get();
+        let student = self.roll_ref_with_pity(&mut state);
+        self.pity_state.set(state);
+        student
+    }
+
+    /// Performs a 10-pull without cloning the Students, see [`Banner::try_roll10`]
+    pub fn try_roll10_ref(&self) -> Option<[&T; 10]> {
+        let mut state = self.pity_state.get();
+        let students = self.try_roll10_ref_with_pity(&mut state);
+        self.pity_state.set(state);
         students
     }
+
+    /// Performs a single pull, progressing towards pity
+    ///
+    /// If the Banner has no pity rules, this is the same as [`Recruitment::roll`].
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::pity::{Pity, PityState};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集")
+    ///     .with_gacha(gacha)
+    ///     .with_pity(Pity::new().with_hard_pity(10))
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let mut state = PityState::default();
+    /// let students: Vec<Student> = (0..10).map(|_| banner.roll_with_pity(&mut state)).collect();
+    ///
+    /// assert!(students.iter().any(|student| student.rarity == Rarity::Three));
+    /// ```
+    pub fn roll_with_pity(&self, state: &mut PityState) -> T {
+        self.roll_ref_with_pity(state).clone()
+    }
+
+    /// Performs a single pull without cloning the Student, see [`Banner::roll_with_pity`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    pub fn roll_ref_with_pity(&self, state: &mut PityState) -> &T {
+        self.roll_ref_with_rng(state, &mut rand::thread_rng())
+    }
+
+    /// Performs a single pull using the given source of randomness, see
+    /// [`Banner::roll_with_pity`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    /// * `rng` - The source of randomness
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::pity::PityState;
+    /// # use blue_gacha::student::Student;
+    /// # use rand::SeedableRng;
+    /// # use rand_chacha::ChaCha8Rng;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// let mut state = PityState::default();
+    /// let first = banner.roll_ref_with_rng(&mut state, &mut ChaCha8Rng::seed_from_u64(7));
+    /// let second = banner.roll_ref_with_rng(&mut state, &mut ChaCha8Rng::seed_from_u64(7));
+    /// assert!(std::ptr::eq(first, second));
+    /// ```
+    pub fn roll_ref_with_rng(&self, state: &mut PityState, rng: &mut dyn RngCore) -> &T {
+        self.roller().pull(state, rng)
+    }
+
+    /// Performs a 10-pull, respecting the Banner's purchase limit and
+    /// progressing towards pity
+    ///
+    /// Will return `None` if the purchase limit has been reached.
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    pub fn try_roll10_with_pity(&self, state: &mut PityState) -> Option<[T; 10]> {
+        self.try_roll10_ref_with_pity(state)
+            .map(|students| students.map(T::clone))
+    }
+
+    /// Performs a 10-pull without cloning the Students, see
+    /// [`Banner::try_roll10_with_pity`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    pub fn try_roll10_ref_with_pity(&self, state: &mut PityState) -> Option<[&T; 10]> {
+        self.try_roll10_ref_with_rng(state, &mut rand::thread_rng())
+    }
+
+    /// Performs a 10-pull using the given source of randomness, see
+    /// [`Banner::try_roll10_with_pity`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    /// * `rng` - The source of randomness
+    pub fn try_roll10_ref_with_rng(
+        &self,
+        state: &mut PityState,
+        rng: &mut dyn RngCore,
+    ) -> Option<[&T; 10]> {
+        self.try_roll10_ref_from(&self.sampler, state, rng)
+    }
+
+    /// Performs a 10-pull from a different Sampler, which counts towards this
+    /// Banner's purchase limit
+    pub(crate) fn try_roll10_ref_from<'a>(
+        &'a self,
+        sampler: &'a Sampler<T>,
+        state: &mut PityState,
+        rng: &mut dyn RngCore,
+    ) -> Option<[&'a T; 10]> {
+        let students = self
+            .roller_with(sampler)
+            .ten_pull(state, self.purchases.get(), rng)?;
+
+        self.purchases.set(self.purchases.get() + 1);
+        Some(students)
+    }
+
+    /// Counts the results of many simulated pulls on this Banner, see [`Simulation`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// let histogram = banner.simulate().with_ten_pulls(20).with_accounts(1000).with_seed(7).run();
+    ///
+    /// assert_eq!(histogram.pulls(), 200_000);
+    /// assert_eq!(histogram.rarities().map(|(_, count)| count).sum::<u64>(), 200_000);
+    /// ```
+    pub fn simulate(&self) -> Simulation<'_, T> {
+        Simulation::new(self)
+    }
+
+    /// Returns the parts of this Banner needed to pull, without any of its
+    /// pull-to-pull state
+    pub(crate) fn roller(&self) -> Roller<'_, T> {
+        self.roller_with(&self.sampler)
+    }
+
+    /// Returns the parts of this Banner needed to pull, with a different Sampler
+    pub(crate) fn roller_with<'a>(&'a self, sampler: &'a Sampler<T>) -> Roller<'a, T> {
+        Roller {
+            sampler,
+            rules: self.rules.as_ref(),
+            purchase_limit: self.purchase_limit,
+            final_guarantee: self.final_guarantee,
+        }
+    }
+
+    /// Creates a Banner which is linked to this one, like the two Banners of a
+    /// double pick-up
+    ///
+    /// The linked Banner shares this Banner's pool, rates and costs, but has
+    /// its own rate-up and sparkable Students. Spark points are tracked
+    /// separately for each Banner, see [`crate::session::Session::spark`].
+    ///
+    /// Will return an error if some of the linked Banner's pulls would have no
+    /// Student to give, see [`Gacha::validate`].
+    ///
+    /// # Arguments
+    /// * `jpn_name` - The name of the linked Banner as seen in Blue Archive
+    /// * `priority` - The Students who have increased rates on the linked Banner
+    /// * `sparkable` - The Students which are sparkable on the linked Banner
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let shiroko = Student::new("シロコ", Rarity::Three);
+    /// let pool = vec![
+    ///     hoshino.clone(),
+    ///     shiroko.clone(),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    ///
+    /// let gacha = GachaBuilder::default()
+    ///     .with_pool(pool)
+    ///     .with_priority(vec![hoshino.clone().into_priority_student(0.7)])
+    ///     .finish()
+    ///     .unwrap();
+    /// let hoshino_banner = BannerBuilder::new("ピックアップ募集（ホシノ）")
+    ///     .with_gacha(gacha)
+    ///     .with_sparkable_students(vec![hoshino.clone()])
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let shiroko_banner = hoshino_banner.link(
+    ///     "ピックアップ募集（シロコ）",
+    ///     vec![shiroko.clone().into_priority_student(0.7)],
+    ///     vec![shiroko.clone()],
+    /// ).unwrap();
+    ///
+    /// assert!(shiroko_banner.is_sparkable(&shiroko));
+    /// assert!(!shiroko_banner.is_sparkable(&hoshino));
+    ///
+    /// // The increased rates add up to more than the 3★ rate
+    /// let both = vec![
+    ///     hoshino.into_priority_student(0.7),
+    ///     shiroko.into_priority_student(2.0),
+    /// ];
+    /// assert!(hoshino_banner.link("ピックアップ募集", both, Vec::new()).is_err());
+    /// ```
+    pub fn link(
+        &self,
+        jpn_name: &str,
+        priority: Vec<PriorityStudent<T>>,
+        sparkable: Vec<T>,
+    ) -> Result<Banner<T>, Error> {
+        let gacha = Gacha {
+            priority: Some(priority).filter(|priority| !priority.is_empty()),
+            ..self.gacha().clone()
+        };
+        gacha.validate()?;
+
+        Ok(Banner {
+            name: I18nString::new(jpn_name),
+            sampler: Sampler::new(gacha),
+            sparkable: Some(sparkable).filter(|sparkable| !sparkable.is_empty()),
+            purchases: Cell::new(0),
+            pity_state: Default::default(),
+            ..self.clone()
+        })
+    }
+}
+
+impl<T: Item> Recruitment<T> for Banner<T> {
+    fn name(&self) -> &I18nString {
+        &self.name
+    }
+
+    fn spark_banner(&self) -> Option<&Banner<T>> {
+        Some(self)
+    }
+
+    fn roll(&self) -> T {
+        let mut state = self.pity_state.get();
+        let student = self.roll_with_pity(&mut state);
+        self.pity_state.set(state);
+        student
+    }
+
+    /// Performs a 10-pull, see [`Banner::try_roll10`]
+    ///
+    /// # Panics
+    /// Panics if the Banner's purchase limit has been reached
+    fn roll10(&self) -> [T; 10] {
+        self.try_roll10()
+            .expect("banner's purchase limit has been reached")
+    }
+
+    fn roll_with_pity(&self, state: &mut PityState) -> T {
+        Banner::roll_with_pity(self, state)
+    }
+
+    /// Performs a 10-pull progressing towards pity, see [`Banner::try_roll10_with_pity`]
+    ///
+    /// # Panics
+    /// Panics if the Banner's purchase limit has been reached
+    fn roll10_with_pity(&self, state: &mut PityState) -> [T; 10] {
+        self.try_roll10_with_pity(state)
+            .expect("banner's purchase limit has been reached")
+    }
+
+    fn try_roll10(&self) -> Option<[T; 10]> {
+        Banner::try_roll10(self)
+    }
+
+    fn ten_pull_cost(&self) -> u32 {
+        Banner::ten_pull_cost(self)
+    }
+
+    fn try_roll10_with_pity(&self, state: &mut PityState) -> Option<[T; 10]> {
+        Banner::try_roll10_with_pity(self, state)
+    }
 }
@@ -0,0 +1,501 @@
+//! A local HTTP server which exposes the simulator as a JSON API
+//!
+//! ```text
+//! blue-gacha-server --students students.json --banners banners.json [--address 127.0.0.1:8080]
+//! ```
+//!
+//! `--banners` takes a JSON array of banner definitions, and can be repeated.
+//!
+//! | Method | Path                                   | Body                                       |
+//! |--------|----------------------------------------|--------------------------------------------|
+//! | GET    | `/banners`                             |                                            |
+//! | GET    | `/banners/{banner}/odds`               |                                            |
+//! | POST   | `/banners/{banner}/roll`               | `{"count": 1 or 10, "seed": 7}`            |
+//! | POST   | `/banners/{banner}/simulate`           | `{"ten_pulls": 20, "accounts": 1000, "seed": 7}` |
+//! | GET    | `/students?q=ホシノ`                    |                                            |
+//! | POST   | `/sessions`                            | `{"pyroxene": 12000}`                      |
+//! | GET    | `/sessions/{id}`                       |                                            |
+//! | POST   | `/sessions/{id}/pyroxene`              | `{"amount": 1200}`                         |
+//! | POST   | `/sessions/{id}/banners/{banner}/roll` | `{"count": 1 or 10, "seed": 7}`            |
+//! | POST   | `/sessions/{id}/banners/{banner}/spark`| `{"student": "ホシノ"}`                     |
+//!
+//! `{banner}` is the index of the banner in `/banners`. Every field of a body is optional.
+//! A session's `spark_points` lists its unspent points on each banner, in the same order.
+//! Banners with a purchase limit only sell 10-pulls.
+//!
+//! Simulations are limited to 10,000 accounts of 1,000 pulls each.
+use blue_gacha::banner::Banner;
+use blue_gacha::database::StudentDatabase;
+use blue_gacha::definition::BannerDefinition;
+use blue_gacha::i18n::{I18nString, Language};
+use blue_gacha::rng;
+use blue_gacha::session::Session;
+use blue_gacha::student::Student;
+use rand::RngCore;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+/// The most accounts a single simulation may run
+const MAX_ACCOUNTS: u64 = 10_000;
+/// The most pulls each account of a simulation may make
+const MAX_PULLS: u64 = 1_000;
+
+/// A client's account, with its own copy of every Banner so that purchase
+/// limits aren't shared between clients
+struct Client {
+    session: Session,
+    pyroxene: u64,
+    banners: Vec<Banner>,
+}
+
+struct State {
+    db: StudentDatabase,
+    banners: Vec<Banner>,
+    clients: HashMap<String, Client>,
+}
+
+/// An error response
+struct ApiError(u16, String);
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self(400, message.into())
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self(404, message.into())
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::bad_request(format!("invalid body: {}", err))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RollBody {
+    count: Option<u32>,
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SimulateBody {
+    pulls: Option<u64>,
+    ten_pulls: Option<u64>,
+    accounts: Option<u64>,
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SessionBody {
+    pyroxene: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PyroxeneBody {
+    amount: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SparkBody {
+    student: String,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut students_path = None;
+    let mut banner_paths = Vec::new();
+    let mut address = DEFAULT_ADDRESS.to_string();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .unwrap_or_else(|| usage(&format!("{} needs a value", arg)));
+        match arg.as_str() {
+            "--students" => students_path = Some(value),
+            "--banners" => banner_paths.push(value),
+            "--address" => address = value,
+            _ => usage(&format!("unknown argument {}", arg)),
+        }
+    }
+
+    let students_path = students_path.unwrap_or_else(|| usage("--students is required"));
+    let db = StudentDatabase::from_json(&read(&students_path))
+        .unwrap_or_else(|err| exit(&format!("{}: {}", students_path, err)));
+
+    let mut banners = Vec::new();
+    for path in banner_paths {
+        let definitions: Vec<BannerDefinition> = serde_json::from_str(&read(&path))
+            .unwrap_or_else(|err| exit(&format!("{}: {}", path, err)));
+        for definition in definitions {
+            banners.push(
+                definition
+                    .build(&db)
+                    .unwrap_or_else(|err| exit(&format!("{}: {}", path, err))),
+            );
+        }
+    }
+
+    let server =
+        Server::http(&address).unwrap_or_else(|err| exit(&format!("{}: {}", address, err)));
+    eprintln!("Listening on http://{}", address);
+
+    let mut state = State {
+        db,
+        banners,
+        clients: HashMap::new(),
+    };
+
+    for mut request in server.incoming_requests() {
+        // A panic only fails the request which caused it
+        let result = panic::catch_unwind(AssertUnwindSafe(|| handle(&mut state, &mut request)));
+        let (status, body) = match result {
+            Ok(Ok(body)) => (200, body),
+            Ok(Err(ApiError(status, message))) => (status, json!({ "error": message })),
+            Err(_) => (500, json!({ "error": "internal server error" })),
+        };
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
+            );
+        if let Err(err) = request.respond(response) {
+            eprintln!("failed to respond: {}", err);
+        }
+    }
+}
+
+fn usage(message: &str) -> ! {
+    exit(&format!(
+        "{}\nusage: blue-gacha-server --students <file> --banners <file>... [--address {}]",
+        message, DEFAULT_ADDRESS
+    ))
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1)
+}
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|err| exit(&format!("{}: {}", path, err)))
+}
+
+fn handle(state: &mut State, request: &mut Request) -> Result<Value, ApiError> {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    let body = if body.trim().is_empty() { "{}" } else { &body };
+
+    match (request.method(), segments.as_slice()) {
+        (Method::Get, ["banners"]) => Ok(state.banners.iter().map(banner_json).collect()),
+        (Method::Get, ["banners", banner, "odds"]) => {
+            Ok(odds_json(find_banner(&state.banners, banner)?))
+        }
+        (Method::Post, ["banners", banner, "roll"]) => {
+            let body: RollBody = serde_json::from_str(body)?;
+            // Stateless pulls ignore purchase limits
+            let banner = find_banner(&state.banners, banner)?.clone();
+            let mut session = Session::new();
+            let students = roll(&mut session, &banner, &body)?;
+            Ok(json!({ "students": students }))
+        }
+        (Method::Post, ["banners", banner, "simulate"]) => {
+            let body: SimulateBody = serde_json::from_str(body)?;
+            simulate(find_banner(&state.banners, banner)?, &body)
+        }
+        (Method::Get, ["students"]) => {
+            let query = query_param(query, "q");
+            let students: Vec<&Student> = match &query {
+                Some(query) => state.db.search(query),
+                None => state.db.students().iter().collect(),
+            };
+            Ok(json!(students))
+        }
+        (Method::Post, ["sessions"]) => {
+            let body: SessionBody = serde_json::from_str(body)?;
+            let id = format!("{:016x}", rand::thread_rng().next_u64());
+            let client = Client {
+                session: Session::new(),
+                pyroxene: body.pyroxene,
+                banners: state.banners.clone(),
+            };
+            let response = client_json(&id, &client);
+            state.clients.insert(id, client);
+            Ok(response)
+        }
+        (Method::Get, ["sessions", id]) => {
+            let client = find_client(&mut state.clients, id)?;
+            Ok(client_json(id, client))
+        }
+        (Method::Post, ["sessions", id, "pyroxene"]) => {
+            let body: PyroxeneBody = serde_json::from_str(body)?;
+            let client = find_client(&mut state.clients, id)?;
+            client.pyroxene += body.amount;
+            Ok(client_json(id, client))
+        }
+        (Method::Post, ["sessions", id, "banners", banner, "roll"]) => {
+            let body: RollBody = serde_json::from_str(body)?;
+            let client = find_client(&mut state.clients, id)?;
+            let banner = find_banner(&client.banners, banner)?;
+
+            let cost = match body.count.unwrap_or(1) {
+                10 => banner.ten_pull_cost() as u64,
+                _ => banner.ten_pull_cost() as u64 / 10,
+            };
+            if client.pyroxene < cost {
+                return Err(ApiError(
+                    402,
+                    format!("{} Pyroxene needed, {} left", cost, client.pyroxene),
+                ));
+            }
+
+            let students = roll(&mut client.session, banner, &body)?;
+            client.pyroxene -= cost;
+            Ok(json!({
+                "students": students,
+                "session": client_json(id, client),
+            }))
+        }
+        (Method::Post, ["sessions", id, "banners", banner, "spark"]) => {
+            let body: SparkBody = serde_json::from_str(body)?;
+            let client = find_client(&mut state.clients, id)?;
+            let banner = find_banner(&client.banners, banner)?;
+
+            if !banner
+                .sparkable()
+                .unwrap_or_default()
+                .iter()
+                .any(|student| student.name == body.student.as_str())
+            {
+                return Err(ApiError(409, "the Student can't be sparked".to_string()));
+            }
+            let threshold = banner
+                .spark_threshold()
+                .ok_or_else(|| ApiError(409, "the banner has no spark".to_string()))?;
+
+            let points = client.session.spark_points(banner);
+            if points < threshold {
+                return Err(ApiError(
+                    409,
+                    format!("{} spark points needed, {} earned", threshold, points),
+                ));
+            }
+            let student = client
+                .session
+                .spark(banner, &body.student)
+                .ok_or_else(|| ApiError(409, "the Student can't be sparked".to_string()))?;
+            Ok(json!({
+                "student": student,
+                "session": client_json(id, client),
+            }))
+        }
+        _ => Err(ApiError::not_found(format!("no route for {}", path))),
+    }
+}
+
+fn find_banner<'a>(banners: &'a [Banner], index: &str) -> Result<&'a Banner, ApiError> {
+    Ok(&banners[banner_index(banners, index)?])
+}
+
+fn banner_index(banners: &[Banner], index: &str) -> Result<usize, ApiError> {
+    index
+        .parse::<usize>()
+        .ok()
+        .filter(|&index| index < banners.len())
+        .ok_or_else(|| ApiError::not_found(format!("no banner {}", index)))
+}
+
+fn find_client<'a>(
+    clients: &'a mut HashMap<String, Client>,
+    id: &str,
+) -> Result<&'a mut Client, ApiError> {
+    clients
+        .get_mut(id)
+        .ok_or_else(|| ApiError::not_found(format!("no session {}", id)))
+}
+
+fn roll(session: &mut Session, banner: &Banner, body: &RollBody) -> Result<Vec<Student>, ApiError> {
+    let mut rng: Box<dyn RngCore> = match body.seed {
+        Some(seed) => Box::new(rng::seeded(seed)),
+        None => Box::new(rand::thread_rng()),
+    };
+
+    match body.count.unwrap_or(1) {
+        // Purchase limits count 10-pulls, which single pulls would get around
+        1 if banner.purchase_limit().is_some() => {
+            Err(ApiError(409, "the banner only sells 10-pulls".to_string()))
+        }
+        1 => Ok(vec![session.roll_with_rng(banner, &mut rng)]),
+        10 => session
+            .roll10_with_rng(banner, &mut rng)
+            .map(|students| students.to_vec())
+            .ok_or_else(|| ApiError(409, "the purchase limit has been reached".to_string())),
+        count => Err(ApiError::bad_request(format!(
+            "can't pull {} at once",
+            count
+        ))),
+    }
+}
+
+fn simulate(banner: &Banner, body: &SimulateBody) -> Result<Value, ApiError> {
+    let accounts = body.accounts.unwrap_or(1);
+    if accounts > MAX_ACCOUNTS {
+        return Err(ApiError::bad_request(format!(
+            "at most {} accounts can be simulated",
+            MAX_ACCOUNTS
+        )));
+    }
+
+    let pulls = match (body.pulls, body.ten_pulls) {
+        (Some(pulls), None) => pulls,
+        (None, Some(ten_pulls)) => ten_pulls.saturating_mul(10),
+        _ => return Err(ApiError::bad_request("set one of pulls or ten_pulls")),
+    };
+    if pulls > MAX_PULLS {
+        return Err(ApiError::bad_request(format!(
+            "at most {} pulls per account can be simulated",
+            MAX_PULLS
+        )));
+    }
+
+    let mut simulation = banner.simulate().with_accounts(accounts);
+    simulation = match body.ten_pulls {
+        Some(ten_pulls) => simulation.with_ten_pulls(ten_pulls),
+        None => simulation.with_pulls(pulls),
+    };
+    if let Some(seed) = body.seed {
+        simulation = simulation.with_seed(seed);
+    }
+
+    let histogram = simulation.run();
+    let students: HashMap<&str, u64> = histogram.students().collect();
+    let rarities: HashMap<String, u64> = histogram
+        .rarities()
+        .map(|(rarity, count)| (rarity.0.to_string(), count))
+        .collect();
+
+    Ok(json!({
+        "pulls": histogram.pulls(),
+        "students": students,
+        "rarities": rarities,
+    }))
+}
+
+fn name_json(name: &I18nString) -> Value {
+    name.translations()
+        .map(|(language, translation)| (language_code(language), json!(translation)))
+        .collect::<serde_json::Map<String, Value>>()
+        .into()
+}
+
+fn language_code(language: Language) -> String {
+    serde_json::to_value(language)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn banner_json(banner: &Banner) -> Value {
+    json!({
+        "name": name_json(&banner.name),
+        "ten_pull_cost": banner.ten_pull_cost(),
+        "spark_threshold": banner.spark_threshold(),
+        "purchase_limit": banner.purchases_remaining(),
+        "sparkable": banner.sparkable().unwrap_or_default(),
+    })
+}
+
+fn odds_json(banner: &Banner) -> Value {
+    let gacha = banner.gacha();
+    let rarities: HashMap<String, f64> = gacha
+        .tiers
+        .rarities()
+        .map(|rarity| (rarity.0.to_string(), gacha.get_rate(rarity) as f64 / 1000.0))
+        .collect();
+    let students: Vec<Value> = gacha
+        .pool
+        .iter()
+        .map(|student| {
+            json!({
+                "student": student,
+                "probability": gacha.probability_of(student),
+            })
+        })
+        .collect();
+
+    json!({
+        "name": name_json(&banner.name),
+        "rarities": rarities,
+        "students": students,
+    })
+}
+
+fn client_json(id: &str, client: &Client) -> Value {
+    json!({
+        "id": id,
+        "pyroxene": client.pyroxene,
+        "pulls": client.session.history().len(),
+        "spark_points": client
+            .banners
+            .iter()
+            .map(|banner| client.session.spark_points(banner))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Returns the percent-decoded value of a query string parameter
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (name == key).then(|| percent_decode(value))
+    })
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,426 @@
+use crate::error::Error;
+use crate::i18n::Language;
+use crate::search::{edit_distance, normalize, romanize};
+use crate::student::{Student, Variant};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A Collection of every Student known to the Gacha Simulator
+///
+/// A StudentDatabase serializes to and from the same JSON Array found in
+/// `./examples/students.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StudentDatabase {
+    students: Vec<Student>,
+}
+
+impl StudentDatabase {
+    /// Creates a new StudentDatabase
+    ///
+    /// # Arguments
+    /// * `students` - Every Student which should be in the database
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::student::Student;
+    /// let db = StudentDatabase::new(vec![Student::new("アル", Rarity::Three)]);
+    /// ```
+    pub fn new(students: Vec<Student>) -> Self {
+        Self { students }
+    }
+
+    /// Deserializes a StudentDatabase from a JSON Array of Students
+    ///
+    /// Missing translations are tolerated, see [`StudentDatabase::from_json_strict`]
+    /// if that is undesirable.
+    ///
+    /// # Arguments
+    /// * `json` - A JSON Array of Students
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// let json = r#"[{ "name": { "translations": { "jpn": "アル" } }, "rarity": 3 }]"#;
+    /// let db = StudentDatabase::from_json(json).unwrap();
+    ///
+    /// assert_eq!(db.students().len(), 1);
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Deserializes a StudentDatabase from a JSON Array of Students, rejecting
+    /// any database which doesn't pass [`StudentDatabase::audit`]
+    ///
+    /// # Arguments
+    /// * `json` - A JSON Array of Students
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::error::Error;
+    /// let json = r#"[{ "name": { "translations": { "jpn": "アル" } }, "rarity": 3 }]"#;
+    ///
+    /// assert!(matches!(
+    ///     StudentDatabase::from_json_strict(json),
+    ///     Err(Error::IncompleteDatabase(_))
+    /// ));
+    /// ```
+    pub fn from_json_strict(json: &str) -> Result<Self, Error> {
+        let db = Self::from_json(json)?;
+        let audit = db.audit();
+
+        if audit.is_complete() {
+            Ok(db)
+        } else {
+            Err(Error::IncompleteDatabase(audit))
+        }
+    }
+
+    /// Returns every Student in the database
+    pub fn students(&self) -> &[Student] {
+        &self.students
+    }
+
+    /// Finds a Student by their Japanese name
+    ///
+    /// # Arguments
+    /// * `jpn_name` - The Japanese name of the Student
+    pub fn find(&self, jpn_name: &str) -> Option<&Student> {
+        self.students
+            .iter()
+            .find(|student| student.name.translation(Language::Japanese) == Some(jpn_name))
+    }
+
+    /// Returns every version of a character, including the base character
+    ///
+    /// # Arguments
+    /// * `base_jpn_name` - The Japanese name of the base character
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::student::{Student, Variant};
+    /// let mut swimsuit_hoshino = Student::new("ホシノ（水着）", Rarity::Three);
+    /// swimsuit_hoshino.set_variant("ホシノ", Variant::Swimsuit);
+    /// let db = StudentDatabase::new(vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     swimsuit_hoshino,
+    ///     Student::new("シロコ", Rarity::Three),
+    /// ]);
+    ///
+    /// assert_eq!(db.versions_of("ホシノ").len(), 2);
+    /// ```
+    pub fn versions_of(&self, base_jpn_name: &str) -> Vec<&Student> {
+        self.students
+            .iter()
+            .filter(|student| student.is_version_of(base_jpn_name))
+            .collect()
+    }
+
+    /// Finds a specific version of a character
+    ///
+    /// # Arguments
+    /// * `base_jpn_name` - The Japanese name of the base character
+    /// * `variant` - The version to find, or `None` for the base character
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::student::{Student, Variant};
+    /// let mut swimsuit_hoshino = Student::new("ホシノ（水着）", Rarity::Three);
+    /// swimsuit_hoshino.set_variant("ホシノ", Variant::Swimsuit);
+    /// let db = StudentDatabase::new(vec![Student::new("ホシノ", Rarity::Three), swimsuit_hoshino]);
+    ///
+    /// let student = db.find_variant("ホシノ", Some(&Variant::Swimsuit)).unwrap();
+    /// assert_eq!(student.name, "ホシノ（水着）");
+    /// assert_eq!(db.find_variant("ホシノ", None).unwrap().name, "ホシノ");
+    /// ```
+    pub fn find_variant(&self, base_jpn_name: &str, variant: Option<&Variant>) -> Option<&Student> {
+        self.students.iter().find(|student| {
+            student.is_version_of(base_jpn_name) && student.variant.as_ref() == variant
+        })
+    }
+
+    /// Resolves a [`StudentRef`] to the Student it refers to
+    ///
+    /// # Arguments
+    /// * `reference` - A reference to a Student, e.g. from a Banner definition
+    pub fn resolve(&self, reference: &StudentRef) -> Option<&Student> {
+        match reference {
+            StudentRef::Name(jpn_name) => self.find(jpn_name),
+            StudentRef::Variant { base, variant } => self.find_variant(base, variant.as_ref()),
+        }
+    }
+
+    /// Searches for Students whose name in any Language matches `query`
+    ///
+    /// Both the query and every name are [normalised](crate::search::normalize),
+    /// and Japanese names are additionally compared in romaji. Exact matches
+    /// come first, followed by names which start with the query, and then
+    /// names which merely contain it.
+    ///
+    /// # Arguments
+    /// * `query` - What the user typed, e.g. "hoshino" or "ﾎｼﾉ"
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::student::Student;
+    /// let db = StudentDatabase::new(vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("シロコ", Rarity::Three),
+    /// ]);
+    ///
+    /// assert_eq!(db.search("hoshino")[0].name, "ホシノ");
+    /// assert_eq!(db.search("ｼﾛｺ")[0].name, "シロコ");
+    /// assert!(db.search("nozomi").is_empty());
+    /// ```
+    pub fn search(&self, query: &str) -> Vec<&Student> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(usize, &Student)> = self
+            .students
+            .iter()
+            .filter_map(|student| {
+                search_keys(student)
+                    .iter()
+                    .filter_map(|key| {
+                        if *key == query {
+                            Some(0)
+                        } else if key.starts_with(&query) {
+                            Some(1)
+                        } else if key.contains(&query) {
+                            Some(2)
+                        } else {
+                            None
+                        }
+                    })
+                    .min()
+                    .map(|rank| (rank, student))
+            })
+            .collect();
+        matches.sort_by_key(|(rank, _)| *rank);
+
+        matches.into_iter().map(|(_, student)| student).collect()
+    }
+
+    /// Suggests Students whose names are similar to `query`, which is useful
+    /// when [`StudentDatabase::search`] comes up empty
+    ///
+    /// Suggestions are sorted by edit distance, closest first. Names further
+    /// than a third of the query's length away are not suggested.
+    ///
+    /// # Arguments
+    /// * `query` - What the user typed
+    /// * `limit` - The maximum amount of suggestions to return
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::i18n::Language;
+    /// # use blue_gacha::student::Student;
+    /// let mut hoshino = Student::new("ホシノ", Rarity::Three);
+    /// hoshino.add_translation(Language::English, "Hoshino");
+    /// let db = StudentDatabase::new(vec![hoshino, Student::new("シロコ", Rarity::Three)]);
+    ///
+    /// let suggestions = db.suggest("hoshimo", 3);
+    /// assert_eq!(suggestions.len(), 1);
+    /// assert_eq!(suggestions[0].student.name, "ホシノ");
+    /// assert_eq!(suggestions[0].distance, 1);
+    /// ```
+    pub fn suggest(&self, query: &str, limit: usize) -> Vec<Suggestion<'_>> {
+        let query = normalize(query);
+        let max_distance = (query.chars().count() / 3).max(1);
+
+        let mut suggestions: Vec<Suggestion> = self
+            .students
+            .iter()
+            .filter_map(|student| {
+                search_keys(student)
+                    .iter()
+                    .map(|key| edit_distance(&query, key))
+                    .min()
+                    .filter(|distance| *distance <= max_distance)
+                    .map(|distance| Suggestion { student, distance })
+            })
+            .collect();
+        suggestions.sort_by_key(|suggestion| suggestion.distance);
+        suggestions.truncate(limit);
+
+        suggestions
+    }
+
+    /// Checks every Student for missing translations and for names which
+    /// collide with another Student's name in the same Language
+    ///
+    /// Names are considered colliding if they're equal after trimming
+    /// whitespace and ignoring case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::database::StudentDatabase;
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::i18n::Language;
+    /// # use blue_gacha::student::Student;
+    /// let mut aru = Student::new("アル", Rarity::Three);
+    /// aru.add_translation(Language::English, "Aru");
+    /// let mut haruka = Student::new("ハルカ", Rarity::One);
+    /// haruka.add_translation(Language::English, "aru ");
+    /// let mutsuki = Student::new("ムツキ", Rarity::Two);
+    ///
+    /// let audit = StudentDatabase::new(vec![aru, haruka, mutsuki]).audit();
+    ///
+    /// assert_eq!(audit.missing[&Language::English], vec!["ムツキ".to_string()]);
+    /// assert_eq!(audit.collisions.len(), 1);
+    /// assert!(!audit.is_complete());
+    /// ```
+    pub fn audit(&self) -> TranslationAudit {
+        let mut audit = TranslationAudit::default();
+
+        for &language in Language::ALL.iter() {
+            let mut seen: HashMap<String, Vec<String>> = HashMap::new();
+
+            for (index, student) in self.students.iter().enumerate() {
+                let jpn_name = audit_label(index, student);
+
+                match student.name.translation(language) {
+                    Some(name) if !name.trim().is_empty() => seen
+                        .entry(name.trim().to_lowercase())
+                        .or_default()
+                        .push(jpn_name),
+                    _ => audit.missing.entry(language).or_default().push(jpn_name),
+                }
+            }
+
+            let mut collisions: Vec<NameCollision> = seen
+                .into_iter()
+                .filter(|(_, students)| students.len() > 1)
+                .map(|(name, students)| NameCollision {
+                    language,
+                    name,
+                    students,
+                })
+                .collect();
+            collisions.sort_by(|left, right| left.name.cmp(&right.name));
+
+            audit.collisions.extend(collisions);
+        }
+
+        audit
+    }
+}
+
+/// An unambiguous reference to a single Student
+///
+/// In JSON, a StudentRef is either the Japanese name of the Student
+/// (e.g. `"ホシノ（水着）"`), or an object naming the base character and
+/// the variant (e.g. `{ "base": "ホシノ", "variant": "swimsuit" }`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StudentRef {
+    Name(String),
+    Variant {
+        base: String,
+        #[serde(default)]
+        variant: Option<Variant>,
+    },
+}
+
+impl std::fmt::Display for StudentRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StudentRef::Name(jpn_name) => f.write_str(jpn_name),
+            StudentRef::Variant {
+                base,
+                variant: None,
+            } => f.write_str(base),
+            StudentRef::Variant {
+                base,
+                variant: Some(variant),
+            } => write!(f, "{}（{}）", base, variant),
+        }
+    }
+}
+
+impl From<&str> for StudentRef {
+    fn from(jpn_name: &str) -> Self {
+        StudentRef::Name(jpn_name.to_string())
+    }
+}
+
+/// Every normalised form of a Student's name which a query may match against
+/// Names a Student in a [`TranslationAudit`] by their Japanese name, or by
+/// another translation (or their index) if they have none
+fn audit_label(index: usize, student: &Student) -> String {
+    student
+        .name
+        .get(Language::Japanese)
+        .or_else(|| {
+            Language::ALL
+                .iter()
+                .find_map(|&language| student.name.get(language))
+        })
+        .unwrap_or_else(|| format!("#{}", index))
+}
+
+fn search_keys(student: &Student) -> Vec<String> {
+    let mut keys: Vec<String> = Language::ALL
+        .iter()
+        .filter_map(|&language| student.name.translation(language))
+        .map(normalize)
+        .collect();
+
+    if let Some(jpn_name) = student.name.translation(Language::Japanese) {
+        keys.push(romanize(&normalize(jpn_name)));
+    }
+
+    keys
+}
+
+/// A Student whose name is similar to a search query
+#[derive(Debug, Clone, Copy)]
+pub struct Suggestion<'a> {
+    pub student: &'a Student,
+    /// The edit distance between the query and the closest of the Student's names
+    pub distance: usize,
+}
+
+/// The result of [`StudentDatabase::audit`]
+#[derive(Debug, Clone, Default)]
+pub struct TranslationAudit {
+    /// The Japanese names of every Student without a translation, keyed by Language
+    ///
+    /// Students without a Japanese name are listed by another of their
+    /// translations, or by their index in the database as `#index`.
+    pub missing: HashMap<Language, Vec<String>>,
+    /// Every name which is shared by more than one Student
+    pub collisions: Vec<NameCollision>,
+}
+
+impl TranslationAudit {
+    /// Returns true if no translations are missing and no names collide
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.collisions.is_empty()
+    }
+}
+
+/// A name which is used by more than one Student in the same Language
+#[derive(Debug, Clone)]
+pub struct NameCollision {
+    pub language: Language,
+    /// The colliding name, trimmed and lowercased
+    pub name: String,
+    /// The Japanese names of every Student using this name
+    pub students: Vec<String>,
+}
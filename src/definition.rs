@@ -0,0 +1,246 @@
+use crate::banner::{Banner, BannerBuilder};
+use crate::database::{StudentDatabase, StudentRef};
+use crate::error::Error;
+use crate::gacha::{GachaBuilder, Item};
+use crate::i18n::{I18nString, Language};
+use crate::student::Student;
+use crate::tier::{per_mille, Tier, TierSet};
+use serde::{Deserialize, Serialize};
+
+/// A serializable description of a Banner
+///
+/// Students are referred to by [`StudentRef`], and are looked up in a
+/// [`StudentDatabase`] when the Banner is built.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::database::StudentDatabase;
+/// # use blue_gacha::definition::BannerDefinition;
+/// # use blue_gacha::gacha::Rarity;
+/// # use blue_gacha::student::Student;
+/// let db = StudentDatabase::new(vec![
+///     Student::new("ホシノ", Rarity::Three),
+///     Student::new("シロコ", Rarity::Three),
+///     Student::new("セリカ", Rarity::Two),
+///     Student::new("ハルカ", Rarity::One),
+/// ]);
+///
+/// let json = r#"{
+///     "name": { "translations": { "jpn": "ピックアップ募集", "eng": "Rate-Up Registration" } },
+///     "priority": [{ "student": "ホシノ", "rate": 0.7 }],
+///     "sparkable": ["ホシノ"]
+/// }"#;
+/// let definition: BannerDefinition = serde_json::from_str(json).unwrap();
+/// let banner = definition.build(&db).unwrap();
+///
+/// assert_eq!(banner.name, "ピックアップ募集");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BannerDefinition {
+    pub name: I18nString,
+    /// The percent chance of pulling each Tier, from the most common to the rarest
+    #[serde(default = "default_rates")]
+    pub rates: Vec<f32>,
+    /// The Tiers of the Banner, which default to Blue Archive's 1★ to 3★
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tiers: Option<Vec<TierDefinition>>,
+    #[serde(default)]
+    pub pool: PoolDefinition,
+    /// Students with increased rates. They are added to the pool if they're not already in it.
+    #[serde(default)]
+    pub priority: Vec<PriorityDefinition>,
+    #[serde(default)]
+    pub sparkable: Vec<StudentRef>,
+}
+
+/// Describes which Students from a [`StudentDatabase`] are in a Banner's pool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolDefinition {
+    /// The Students in the pool. If absent, every permanent (not limited)
+    /// Student in the database is included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<StudentRef>>,
+    /// Students which are removed from the pool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<StudentRef>,
+}
+
+/// A Student with an increased rate, see [`crate::student::PriorityStudent`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityDefinition {
+    pub student: StudentRef,
+    /// The percent chance of pulling this Student
+    pub rate: f32,
+}
+
+/// A Tier of a Banner with its own rarity system, see [`Tier`]
+///
+/// Its rate is taken from [`BannerDefinition::rates`].
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::database::StudentDatabase;
+/// # use blue_gacha::definition::BannerDefinition;
+/// # use blue_gacha::gacha::Rarity;
+/// # use blue_gacha::student::Student;
+/// let db = StudentDatabase::new(vec![
+///     Student::new("ホシノ", Rarity::Two),
+///     Student::new("ハルカ", Rarity::One),
+/// ]);
+///
+/// let json = r#"{
+///     "name": { "translations": { "jpn": "常設募集" } },
+///     "rates": [97.0, 3.0],
+///     "tiers": [
+///         { "name": { "translations": { "jpn": "R" } }, "symbol": "R" },
+///         { "name": { "translations": { "jpn": "SSR" } }, "symbol": "SSR" }
+///     ]
+/// }"#;
+/// let definition: BannerDefinition = serde_json::from_str(json).unwrap();
+/// let banner = definition.build(&db).unwrap();
+///
+/// assert_eq!(banner.gacha().tiers.get(Rarity::Two).unwrap().symbol, "SSR");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierDefinition {
+    pub name: I18nString,
+    /// How the Tier is shown next to a Student, such as "★★★" or "SSR"
+    pub symbol: String,
+}
+
+fn default_rates() -> Vec<f32> {
+    vec![79.0, 18.5, 2.5]
+}
+
+impl BannerDefinition {
+    /// Returns every Student in this Banner's pool, including priority Students
+    ///
+    /// # Arguments
+    /// * `db` - The database Students are looked up in
+    pub fn pool(&self, db: &StudentDatabase) -> Result<Vec<Student>, Error> {
+        let mut pool: Vec<Student> = match &self.pool.include {
+            Some(include) => resolve_all(db, include)?,
+            None => db
+                .students()
+                .iter()
+                .filter(|student| !student.limited)
+                .cloned()
+                .collect(),
+        };
+
+        // Students are identified by their Japanese name, see `Item::id`
+        if pool
+            .iter()
+            .any(|student| student.name.translation(Language::Japanese).is_none())
+        {
+            return Err(Error::InvalidBanner("every student needs a Japanese name"));
+        }
+
+        let exclude = resolve_all(db, &self.pool.exclude)?;
+        pool.retain(|student| !exclude.iter().any(|excluded| excluded.id() == student.id()));
+
+        for priority in self.priority.iter() {
+            let student = resolve(db, &priority.student)?;
+
+            if !pool.iter().any(|other| other.id() == student.id()) {
+                pool.push(student);
+            }
+        }
+
+        Ok(pool)
+    }
+
+    /// Returns true if the referenced Student has an increased rate on this Banner
+    ///
+    /// # Arguments
+    /// * `db` - The database Students are looked up in
+    /// * `student` - The Student to check for
+    pub fn is_priority(&self, db: &StudentDatabase, student: &Student) -> bool {
+        self.priority
+            .iter()
+            .filter_map(|priority| db.resolve(&priority.student))
+            .any(|priority| {
+                priority.name.translation(Language::Japanese)
+                    == student.name.translation(Language::Japanese)
+            })
+    }
+
+    /// Builds the Banner this definition describes
+    ///
+    /// Will return [`Error::InvalidBanner`] if the Banner or one of its Students
+    /// has no Japanese name, if there isn't one rate per Tier, if the rates
+    /// don't add up to 100%, or if some pulls would have no Student to give,
+    /// see [`crate::gacha::Gacha::validate`].
+    ///
+    /// # Arguments
+    /// * `db` - The database Students are looked up in
+    pub fn build(&self, db: &StudentDatabase) -> Result<Banner, Error> {
+        let jpn_name = self
+            .name
+            .translation(Language::Japanese)
+            .ok_or(Error::InvalidBanner("the banner needs a Japanese name"))?;
+
+        let tiers = match &self.tiers {
+            Some(tiers) if tiers.len() == self.rates.len() => TierSet::new(
+                tiers
+                    .iter()
+                    .zip(self.rates.iter())
+                    .map(|(tier, &rate)| Tier {
+                        name: tier.name.clone(),
+                        symbol: tier.symbol.clone(),
+                        rate: per_mille(rate),
+                    })
+                    .collect(),
+            ),
+            Some(_) => None,
+            None => TierSet::blue_archive().with_rates(&self.rates),
+        }
+        .ok_or(Error::InvalidBanner(
+            "rates must have one rate per tier and add up to 100%",
+        ))?;
+
+        let priority = self
+            .priority
+            .iter()
+            .map(
+                |priority| Ok(resolve(db, &priority.student)?.into_priority_student(priority.rate)),
+            )
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut gacha = GachaBuilder::default()
+            .with_tiers(tiers)
+            .with_pool(self.pool(db)?);
+        if !priority.is_empty() {
+            gacha = gacha.with_priority(priority);
+        }
+        let gacha = gacha
+            .finish()
+            .ok_or(Error::InvalidBanner("missing gacha pool"))?;
+        gacha.validate()?;
+
+        let mut builder = BannerBuilder::new(jpn_name).with_gacha(gacha);
+        for (language, name) in self.name.translations() {
+            builder = builder.with_name_translation(language, name);
+        }
+        if !self.sparkable.is_empty() {
+            builder = builder.with_sparkable_students(resolve_all(db, &self.sparkable)?);
+        }
+
+        builder
+            .finish()
+            .ok_or(Error::InvalidBanner("missing gacha"))
+    }
+}
+
+fn resolve(db: &StudentDatabase, reference: &StudentRef) -> Result<Student, Error> {
+    db.resolve(reference)
+        .cloned()
+        .ok_or_else(|| Error::UnknownStudent(reference.clone()))
+}
+
+fn resolve_all(db: &StudentDatabase, references: &[StudentRef]) -> Result<Vec<Student>, Error> {
+    references
+        .iter()
+        .map(|reference| resolve(db, reference))
+        .collect()
+}
@@ -0,0 +1,45 @@
+use crate::database::{StudentRef, TranslationAudit};
+
+/// The Errors which can occur while loading or validating gacha data
+#[derive(Debug)]
+pub enum Error {
+    /// The provided JSON could not be deserialized
+    Json(serde_json::Error),
+    /// A Student Database was loaded in strict mode, but the audit found problems
+    IncompleteDatabase(TranslationAudit),
+    /// A Banner definition refers to a Student who isn't in the Student Database
+    UnknownStudent(StudentRef),
+    /// A Banner definition can't be turned into a Banner
+    InvalidBanner(&'static str),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Json(err) => write!(f, "invalid JSON: {}", err),
+            Error::IncompleteDatabase(audit) => write!(
+                f,
+                "student database is incomplete: {} missing translation(s), {} name collision(s)",
+                audit.missing.values().map(Vec::len).sum::<usize>(),
+                audit.collisions.len()
+            ),
+            Error::UnknownStudent(reference) => write!(f, "unknown student: {}", reference),
+            Error::InvalidBanner(reason) => write!(f, "invalid banner: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
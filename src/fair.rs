@@ -0,0 +1,381 @@
+//! Provably fair pulls, using a commit–reveal scheme
+//!
+//! 1. The server picks a secret [`ServerSeed`], and publishes its [`Commitment`]
+//!    before any pull is made.
+//! 2. The client picks a nonce, so the server can't choose a seed which
+//!    favours a known outcome.
+//! 3. Every pull is made with randomness derived from the seed, the nonce and
+//!    the index of the pull, see [`pull_rng`].
+//! 4. Once the pulls are over, the server reveals the seed in a [`Transcript`],
+//!    and anyone holding the Banner can [`verify`] every result.
+use crate::banner::{Banner, Roller};
+use crate::gacha::Item;
+use crate::pity::PityState;
+use crate::rng::{SeededRng, SAMPLING_VERSION};
+use crate::student::Student;
+use rand::SeedableRng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+/// The secret seed every pull is derived from
+///
+/// Its `Debug` output only shows the seed's [`Commitment`], so that logging
+/// it doesn't reveal the seed early. Use `Display` to show the seed itself.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::fair::ServerSeed;
+/// let seed = ServerSeed::new([7; 32]);
+/// assert!(!format!("{:?}", seed).contains(&seed.to_string()));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ServerSeed([u8; 32]);
+
+impl std::fmt::Debug for ServerSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerSeed")
+            .field("commitment", &format_args!("{}", self.commitment()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl ServerSeed {
+    /// Creates a ServerSeed from its bytes
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Creates a random ServerSeed
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+
+    /// Parses a ServerSeed from 64 hexadecimal digits
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        from_hex(hex).map(Self)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns the SHA-256 hash of this seed, which can be published without
+    /// giving the seed away
+    pub fn commitment(&self) -> Commitment {
+        Commitment(Sha256::digest(self.0).into())
+    }
+}
+
+impl std::fmt::Display for ServerSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_hex(&self.0, f)
+    }
+}
+
+/// The SHA-256 hash of a [`ServerSeed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    /// Parses a Commitment from 64 hexadecimal digits
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        from_hex(hex).map(Self)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns true if this is the Commitment of the given seed
+    ///
+    /// # Arguments
+    /// * `seed` - The revealed seed
+    pub fn matches(&self, seed: &ServerSeed) -> bool {
+        *self == seed.commitment()
+    }
+}
+
+impl std::fmt::Display for Commitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_hex(&self.0, f)
+    }
+}
+
+fn write_hex(bytes: &[u8], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+}
+
+fn from_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+macro_rules! hex_serde {
+    ($type:ty, $expected:literal) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let hex = String::deserialize(deserializer)?;
+                <$type>::from_hex(&hex).ok_or_else(|| serde::de::Error::custom($expected))
+            }
+        }
+    };
+}
+
+hex_serde!(ServerSeed, "expected a seed of 64 hexadecimal digits");
+hex_serde!(Commitment, "expected a commitment of 64 hexadecimal digits");
+
+/// Returns the source of randomness of a single pull or 10-pull
+///
+/// The RNG is seeded with the SHA-256 hash of the seed, the client's nonce
+/// and the index of the pull, so every pull can be replayed on its own.
+///
+/// # Arguments
+/// * `seed` - The server's seed
+/// * `client_nonce` - The nonce chosen by the client
+/// * `index` - How many pulls and 10-pulls were made before this one
+pub fn pull_rng(seed: &ServerSeed, client_nonce: &str, index: u64) -> SeededRng {
+    let key = Sha256::new()
+        .chain_update(seed.0)
+        .chain_update((client_nonce.len() as u64).to_le_bytes())
+        .chain_update(client_nonce)
+        .chain_update(index.to_le_bytes())
+        .finalize();
+
+    SeededRng::from_seed(key.into())
+}
+
+/// The record of every pull made with a [`FairBanner`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transcript {
+    /// The [`SAMPLING_VERSION`] the pulls were made with
+    pub version: u32,
+    pub commitment: Commitment,
+    pub client_nonce: String,
+    /// The [`Item::id`]s of the Students of every pull, in order. Single
+    /// pulls have one Student, and 10-pulls have ten.
+    pub pulls: Vec<Vec<String>>,
+    /// The server's seed, once it has been revealed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<ServerSeed>,
+}
+
+/// Pulls on a Banner using randomness derived from a committed [`ServerSeed`]
+///
+/// A FairBanner keeps its own pity progress and purchases, starting from
+/// none, so that a [`Transcript`] can be replayed from its first pull.
+pub struct FairBanner<'a, T: Item = Student> {
+    roller: Roller<'a, T>,
+    seed: ServerSeed,
+    client_nonce: String,
+    state: PityState,
+    purchases: u32,
+    pulls: Vec<Vec<String>>,
+}
+
+impl<'a, T: Item> FairBanner<'a, T> {
+    /// Starts pulling on a Banner
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    /// * `seed` - The server's seed, whose Commitment was published beforehand
+    /// * `client_nonce` - The nonce chosen by the client
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::fair::{verify, FairBanner, ServerSeed};
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// let seed = ServerSeed::random();
+    /// let commitment = seed.commitment(); // Published before pulling
+    ///
+    /// let mut fair = FairBanner::new(&banner, seed, "chosen by the client");
+    /// fair.roll();
+    /// fair.try_roll10().unwrap();
+    ///
+    /// let transcript = fair.reveal();
+    /// assert_eq!(transcript.commitment, commitment);
+    /// assert!(verify(&banner, &transcript).is_ok());
+    /// ```
+    pub fn new(banner: &'a Banner<T>, seed: ServerSeed, client_nonce: &str) -> Self {
+        Self {
+            roller: banner.roller(),
+            seed,
+            client_nonce: client_nonce.to_string(),
+            state: PityState::default(),
+            purchases: 0,
+            pulls: Vec::new(),
+        }
+    }
+
+    /// Returns the Commitment of the seed pulls are derived from
+    pub fn commitment(&self) -> Commitment {
+        self.seed.commitment()
+    }
+
+    fn next_rng(&self) -> SeededRng {
+        pull_rng(&self.seed, &self.client_nonce, self.pulls.len() as u64)
+    }
+
+    /// Performs a single pull
+    pub fn roll(&mut self) -> &'a T {
+        let mut rng = self.next_rng();
+        let student = self.roller.pull(&mut self.state, &mut rng);
+        self.pulls.push(vec![student.id().to_string()]);
+        student
+    }
+
+    /// Performs a 10-pull, respecting the Banner's purchase limit
+    ///
+    /// Will return `None` if the purchase limit has been reached.
+    pub fn try_roll10(&mut self) -> Option<[&'a T; 10]> {
+        let mut rng = self.next_rng();
+        let students = self
+            .roller
+            .ten_pull(&mut self.state, self.purchases, &mut rng)?;
+
+        self.purchases += 1;
+        self.pulls
+            .push(students.iter().map(|s| s.id().to_string()).collect());
+        Some(students)
+    }
+
+    /// Returns the Transcript of every pull so far, without revealing the seed
+    pub fn transcript(&self) -> Transcript {
+        Transcript {
+            version: SAMPLING_VERSION,
+            commitment: self.commitment(),
+            client_nonce: self.client_nonce.clone(),
+            pulls: self.pulls.clone(),
+            seed: None,
+        }
+    }
+
+    /// Ends pulling, returning the Transcript of every pull along with the seed
+    pub fn reveal(self) -> Transcript {
+        Transcript {
+            seed: Some(self.seed.clone()),
+            ..self.transcript()
+        }
+    }
+}
+
+/// The reasons a Transcript can fail verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The pulls were made with another version of the sampling procedure,
+    /// so they can't be replayed by this version of the crate
+    VersionMismatch(u32),
+    /// The Transcript's seed hasn't been revealed
+    NotRevealed,
+    /// The revealed seed isn't the one which was committed to
+    CommitmentMismatch,
+    /// Replaying the pull at this index gives different Students
+    PullMismatch(usize),
+}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationError::VersionMismatch(version) => write!(
+                f,
+                "the pulls were made with sampling version {}, not {}",
+                version, SAMPLING_VERSION
+            ),
+            VerificationError::NotRevealed => write!(f, "the seed hasn't been revealed"),
+            VerificationError::CommitmentMismatch => {
+                write!(f, "the seed doesn't match the commitment")
+            }
+            VerificationError::PullMismatch(index) => {
+                write!(f, "pull {} doesn't match the seed", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+/// Replays every pull of a revealed Transcript, checking that the seed
+/// matches its Commitment and that every pull gives the recorded Students
+///
+/// # Arguments
+/// * `banner` - The Banner the pulls were made on
+/// * `transcript` - The revealed Transcript
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::BannerBuilder;
+/// # use blue_gacha::fair::{verify, FairBanner, ServerSeed, VerificationError};
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+/// # use blue_gacha::student::Student;
+/// # let pool = vec![Student::new("ホシノ", Rarity::Three), Student::new("ハルカ", Rarity::One)];
+/// # let gacha = GachaBuilder::new(97.5, 0.0, 2.5).with_pool(pool).finish().unwrap();
+/// # let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+/// let mut fair = FairBanner::new(&banner, ServerSeed::new([7; 32]), "nonce");
+/// let student = fair.roll();
+///
+/// let mut transcript = fair.reveal();
+/// transcript.pulls[0][0] = if student.name == "ホシノ" { "ハルカ" } else { "ホシノ" }.to_string();
+///
+/// assert_eq!(verify(&banner, &transcript), Err(VerificationError::PullMismatch(0)));
+/// ```
+pub fn verify<T: Item>(
+    banner: &Banner<T>,
+    transcript: &Transcript,
+) -> Result<(), VerificationError> {
+    if transcript.version != SAMPLING_VERSION {
+        return Err(VerificationError::VersionMismatch(transcript.version));
+    }
+
+    let seed = transcript
+        .seed
+        .as_ref()
+        .ok_or(VerificationError::NotRevealed)?;
+    if !transcript.commitment.matches(seed) {
+        return Err(VerificationError::CommitmentMismatch);
+    }
+
+    let mut fair = FairBanner::new(banner, seed.clone(), &transcript.client_nonce);
+
+    for (index, pull) in transcript.pulls.iter().enumerate() {
+        let replayed: Vec<&T> = match pull.len() {
+            1 => vec![fair.roll()],
+            10 => fair
+                .try_roll10()
+                .ok_or(VerificationError::PullMismatch(index))?
+                .to_vec(),
+            _ => return Err(VerificationError::PullMismatch(index)),
+        };
+
+        if !replayed
+            .iter()
+            .map(|s| s.id())
+            .eq(pull.iter().map(String::as_str))
+        {
+            return Err(VerificationError::PullMismatch(index));
+        }
+    }
+
+    Ok(())
+}
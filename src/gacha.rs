@@ -1,55 +1,139 @@
+use crate::banner::{Banner, PULL_COST};
+use crate::error::Error;
+use crate::i18n::{I18nString, Language, Localize};
+use crate::pity::PityState;
 use crate::student::{PriorityStudent, Student};
-use serde_repr::{Deserialize_repr, Serialize_repr};
-use std::cmp::Ordering;
-
-const THREE_STAR_RATE: usize = 25;
-const TWO_STAR_RATE: usize = 185;
-const ONE_STAR_RATE: usize = 790;
-
-#[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq)]
-#[repr(u8)]
-/// The Available Rarities in Blue Archive's Gacha System
-pub enum Rarity {
-    One = 1,
-    Two,
-    Three,
+use crate::tier::{per_mille, Tier, TierSet};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+/// The Rarity of an Item, which is its position in a Gacha's [`TierSet`]
+///
+/// Rarity 1 is the most common Tier. Blue Archive's 1★ to 3★ are available
+/// as constants.
+pub struct Rarity(pub u8);
+
+#[allow(non_upper_case_globals)]
+impl Rarity {
+    pub const One: Rarity = Rarity(1);
+    pub const Two: Rarity = Rarity(2);
+    pub const Three: Rarity = Rarity(3);
+}
+
+impl Default for Rarity {
+    fn default() -> Self {
+        Rarity::One
+    }
 }
 
 impl std::fmt::Display for Rarity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            Rarity::One => f.write_str("1★"),
-            Rarity::Two => f.write_str("2★"),
-            Rarity::Three => f.write_str("3★"),
-        }
+        self.fmt_localized(Language::Japanese, f)
     }
 }
 
-impl Default for Rarity {
-    fn default() -> Self {
-        Self::One
+impl Localize for Rarity {
+    /// A Rarity on its own doesn't know which [`TierSet`] it belongs to, so
+    /// it is shown as the Tier of the same Rarity in [`TierSet::default`].
+    /// Use [`Gacha::tier_of`] to show an Item's Rarity in its Gacha's Tiers.
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match TierSet::default().get(*self) {
+            Some(tier) => tier.fmt_localized(language, f),
+            None => write!(f, "Rarity {}", self.0),
+        }
     }
 }
 
-impl PartialOrd for Rarity {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(match (*self, other) {
-            (Rarity::One, Rarity::One) => Ordering::Equal,
-            (Rarity::One, Rarity::Two) => Ordering::Less,
-            (Rarity::One, Rarity::Three) => Ordering::Less,
-            (Rarity::Two, Rarity::One) => Ordering::Greater,
-            (Rarity::Two, Rarity::Two) => Ordering::Equal,
-            (Rarity::Two, Rarity::Three) => Ordering::Less,
-            (Rarity::Three, Rarity::One) => Ordering::Greater,
-            (Rarity::Three, Rarity::Two) => Ordering::Greater,
-            (Rarity::Three, Rarity::Three) => Ordering::Equal,
-        })
+/// Anything which can be pulled from a Gacha
+///
+/// Gachas, Banners and RuleSets are generic over Item, with [`Student`] as
+/// the default.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::{Banner, BannerBuilder};
+/// # use blue_gacha::gacha::{GachaBuilder, Item, Rarity, Recruitment};
+/// #[derive(Debug, Clone)]
+/// struct Furniture {
+///     name: String,
+///     rarity: Rarity,
+/// }
+///
+/// impl Item for Furniture {
+///     fn id(&self) -> &str {
+///         &self.name
+///     }
+///
+///     fn rarity(&self) -> Rarity {
+///         self.rarity
+///     }
+/// }
+///
+/// let pool = vec![
+///     Furniture { name: "Sofa".into(), rarity: Rarity::Three },
+///     Furniture { name: "Desk".into(), rarity: Rarity::Two },
+///     Furniture { name: "Chair".into(), rarity: Rarity::One },
+/// ];
+/// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+/// let banner: Banner<Furniture> = BannerBuilder::new("家具募集").with_gacha(gacha).finish().unwrap();
+///
+/// let furniture: [Furniture; 10] = banner.roll10();
+/// ```
+pub trait Item: Clone + std::fmt::Debug + 'static {
+    /// Uniquely identifies this Item within a Gacha
+    fn id(&self) -> &str;
+
+    /// The Item's Tier within its Gacha's [`TierSet`]
+    fn rarity(&self) -> Rarity;
+
+    /// Limited Items are excluded from Tickets which only allow permanent Items
+    fn is_limited(&self) -> bool {
+        false
     }
 }
 
-impl Ord for Rarity {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+/// Shared Items are Items too, which makes every pull a reference count
+/// increment rather than a deep clone
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::{Banner, BannerBuilder};
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity, Recruitment};
+/// # use blue_gacha::student::Student;
+/// # use std::sync::Arc;
+/// let pool: Vec<Arc<Student>> = vec![
+///     Arc::new(Student::new("ホシノ", Rarity::Three)),
+///     Arc::new(Student::new("セリカ", Rarity::Two)),
+///     Arc::new(Student::new("ハルカ", Rarity::One)),
+/// ];
+/// let gacha = GachaBuilder::default().with_pool(pool.clone()).finish().unwrap();
+/// let banner: Banner<Arc<Student>> = BannerBuilder::new("ピックアップ募集")
+///     .with_gacha(gacha)
+///     .finish()
+///     .unwrap();
+///
+/// let students = banner.roll10();
+/// assert!(students.iter().all(|student| pool.iter().any(|other| Arc::ptr_eq(other, student))));
+/// ```
+impl<T: Item> Item for Arc<T> {
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn rarity(&self) -> Rarity {
+        (**self).rarity()
+    }
+
+    fn is_limited(&self) -> bool {
+        (**self).is_limited()
     }
 }
 
@@ -57,34 +141,87 @@ impl Ord for Rarity {
 /// Representing single and 10-rolls
 ///
 /// Every Banner is expected to implement the Recruitment trait
-pub trait Recruitment {
-    fn roll(&self) -> Student;
-    fn roll10(&self) -> [Student; 10];
+pub trait Recruitment<T: Item = Student> {
+    /// The name of the Banner, which is used to identify it in a pull history
+    fn name(&self) -> &I18nString;
+    fn roll(&self) -> T;
+
+    /// Performs a 10-pull
+    ///
+    /// # Panics
+    /// Banners which can't always be pulled on, such as ones with a purchase
+    /// limit, panic once they can't be pulled on anymore. Use
+    /// [`Recruitment::try_roll10`] if that can happen.
+    fn roll10(&self) -> [T; 10];
+
+    /// Performs a 10-pull, or returns `None` if the Banner can't be pulled on
+    /// anymore, such as when its purchase limit has been reached
+    fn try_roll10(&self) -> Option<[T; 10]> {
+        Some(self.roll10())
+    }
+
+    /// The amount of Pyroxene the next 10-pull costs. A single pull costs a
+    /// tenth of this.
+    fn ten_pull_cost(&self) -> u32 {
+        PULL_COST * 10
+    }
+
+    /// The Banner whose spark points pulls on this earn, see
+    /// [`crate::session::Session::spark`]. Pulls earn no spark points if this
+    /// is `None`.
+    fn spark_banner(&self) -> Option<&Banner<T>> {
+        None
+    }
+
+    /// Performs a single pull, progressing towards pity. Banners without
+    /// pity rules ignore `state`.
+    fn roll_with_pity(&self, state: &mut PityState) -> T {
+        let _ = state;
+        self.roll()
+    }
+
+    /// Performs a 10-pull, progressing towards pity. Banners without pity
+    /// rules ignore `state`.
+    ///
+    /// # Panics
+    /// Panics if the Banner can't be pulled on anymore, see
+    /// [`Recruitment::roll10`]
+    fn roll10_with_pity(&self, state: &mut PityState) -> [T; 10] {
+        let _ = state;
+        self.roll10()
+    }
+
+    /// Performs a 10-pull progressing towards pity, or returns `None` if the
+    /// Banner can't be pulled on anymore, such as when its purchase limit has
+    /// been reached
+    fn try_roll10_with_pity(&self, state: &mut PityState) -> Option<[T; 10]> {
+        Some(self.roll10_with_pity(state))
+    }
 }
 
 /// Gacha Structs are built using this GachaBuilder Class
 ///
-/// By Default, GachaBuilder assumes the rates:
+/// By Default, GachaBuilder assumes Blue Archive's Tiers and rates:
 /// * 1★: 79.0%
 /// * 2★: 18.5%
 /// * 3★: 2.5%
-pub struct GachaBuilder {
-    rates: Option<(usize, usize, usize)>,
-    pool: Option<Vec<Student>>,
-    priority: Option<Vec<PriorityStudent>>,
+pub struct GachaBuilder<T = Student> {
+    tiers: Option<TierSet>,
+    pool: Option<Vec<T>>,
+    priority: Option<Vec<PriorityStudent<T>>>,
 }
 
-impl Default for GachaBuilder {
+impl<T> Default for GachaBuilder<T> {
     fn default() -> Self {
         Self {
-            rates: Some((ONE_STAR_RATE, TWO_STAR_RATE, THREE_STAR_RATE)),
+            tiers: Some(TierSet::blue_archive()),
             pool: Default::default(),
             priority: Default::default(),
         }
     }
 }
 
-impl GachaBuilder {
+impl<T: Item> GachaBuilder<T> {
     /// Creates a new instance of a GachaBuilder.
     ///
     ///
@@ -93,6 +230,10 @@ impl GachaBuilder {
     /// * `two` - The percent chance of pulling a 2★ Student
     /// * `three` - The percent chance of pulling a 3★ Student
     ///
+    /// # Panics
+    /// Panics if the rates don't add up to 100%, see [`TierSet::with_rates`]
+    /// for a fallible alternative
+    ///
     /// # Examples
     /// ```
     /// # use blue_gacha::gacha::GachaBuilder;
@@ -100,18 +241,61 @@ impl GachaBuilder {
     ///     .with_pool(Vec::new());
     /// ```
     pub fn new(one: f32, two: f32, three: f32) -> Self {
-        let one = (one * 10.0) as usize;
-        let two = (two * 10.0) as usize;
-        let three = (three * 10.0) as usize;
+        let one = per_mille(one);
+        let two = per_mille(two);
+        let three = per_mille(three);
 
         assert_eq!(one + two + three, 1000);
 
         Self {
-            rates: Some((one, two, three)),
+            tiers: Some(TierSet::blue_archive_with_rates(one, two, three)),
             ..Default::default()
         }
     }
 
+    /// Replaces Blue Archive's Tiers with another rarity system
+    ///
+    /// # Arguments
+    /// * `tiers` - The Tiers of the Gacha, along with their rates
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Item, Rarity};
+    /// # use blue_gacha::tier::{Tier, TierSet};
+    /// #[derive(Debug, Clone)]
+    /// struct Card(&'static str, Rarity);
+    ///
+    /// impl Item for Card {
+    ///     fn id(&self) -> &str {
+    ///         self.0
+    ///     }
+    ///
+    ///     fn rarity(&self) -> Rarity {
+    ///         self.1
+    ///     }
+    /// }
+    ///
+    /// let tiers = TierSet::new(vec![
+    ///     Tier::new("R", "R", 82.0),
+    ///     Tier::new("SR", "SR", 15.0),
+    ///     Tier::new("SSR", "SSR", 3.0),
+    /// ])
+    /// .unwrap();
+    /// let gacha = GachaBuilder::default()
+    ///     .with_tiers(tiers)
+    ///     .with_pool(vec![Card("Ace", Rarity(3)), Card("Knight", Rarity(1))])
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(gacha.get_rate(Rarity(3)), 30);
+    /// ```
+    pub fn with_tiers(self, tiers: TierSet) -> Self {
+        Self {
+            tiers: Some(tiers),
+            ..self
+        }
+    }
+
     /// Attaches a Student Gacha Pool to the GachaBuilder
     ///
     /// # Arguments
@@ -126,7 +310,7 @@ impl GachaBuilder {
     /// let hina = Student::new("ヒナ", Rarity::Three);
     /// let gacha_builder = GachaBuilder::default().with_pool(vec![aru, hina]);
     /// ```
-    pub fn with_pool(self, students: Vec<Student>) -> Self {
+    pub fn with_pool(self, students: Vec<T>) -> Self {
         Self {
             pool: Some(students),
             ..self
@@ -149,9 +333,9 @@ impl GachaBuilder {
     /// let priority = vec![aru, hina];
     /// let gacha_builder = GachaBuilder::new(79.0, 18.5, 2.5)
     ///     .with_pool(pool)
-    ///     .with_priority(&priority);
+    ///     .with_priority(priority);
     /// ```
-    pub fn with_priority(self, students: Vec<PriorityStudent>) -> Self {
+    pub fn with_priority(self, students: Vec<PriorityStudent<T>>) -> Self {
         Self {
             priority: Some(students),
             ..self
@@ -160,7 +344,7 @@ impl GachaBuilder {
 
     /// Consumes a GachaBuilder and returns a Gacha Struct.
     ///
-    /// Will return `None` if the `rates` or `pool` property of
+    /// Will return `None` if the `tiers` or `pool` property of
     /// GachaBuilder have not been set.
     ///
     /// # Examples
@@ -173,26 +357,67 @@ impl GachaBuilder {
     ///     .with_pool(vec![aru, hina])
     ///     .finish().unwrap();
     /// ```
-    pub fn finish(self) -> Option<Gacha> {
+    pub fn finish(self) -> Option<Gacha<T>> {
         Some(Gacha {
-            rates: self.rates?,
+            tiers: self.tiers?,
             pool: self.pool?,
             priority: self.priority,
         })
     }
 }
 
+/// A Recruitment Ticket, which is exchanged for a single Student of at
+/// least a certain Rarity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ticket {
+    pub name: I18nString,
+    /// The minimum Rarity of the Student obtained with this Ticket
+    pub min_rarity: Rarity,
+    /// Whether limited Students are excluded
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub permanent_only: bool,
+}
+
+impl Ticket {
+    /// Creates a new Ticket
+    ///
+    /// # Arguments
+    /// * `jpn_name` - The name of the Ticket as seen in Blue Archive
+    /// * `min_rarity` - The minimum Rarity of the Student obtained with this Ticket
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{Rarity, Ticket};
+    /// let ticket = Ticket::new("★3確定募集チケット", Rarity::Three).permanent_only();
+    /// ```
+    pub fn new(jpn_name: &str, min_rarity: Rarity) -> Self {
+        Self {
+            name: I18nString::new(jpn_name),
+            min_rarity,
+            permanent_only: false,
+        }
+    }
+
+    /// Excludes limited Students from the Ticket
+    pub fn permanent_only(self) -> Self {
+        Self {
+            permanent_only: true,
+            ..self
+        }
+    }
+}
+
 /// Provides the necessary information to facilitate a "pull", which is
 /// to randomly select a Student from the gacha pool
 #[derive(Debug, Default, Clone)]
-pub struct Gacha {
-    /// (1★, 2★, 3★)
-    pub rates: (usize, usize, usize),
-    pub pool: Vec<Student>,
-    pub priority: Option<Vec<PriorityStudent>>,
+pub struct Gacha<T = Student> {
+    /// The rarity system of the Gacha, along with the rate of each Tier
+    pub tiers: TierSet,
+    pub pool: Vec<T>,
+    pub priority: Option<Vec<PriorityStudent<T>>>,
 }
 
-impl Gacha {
+impl<T: Item> Gacha<T> {
     /// Returns a usize representing the percent chance of pulling a specific rarity
     /// (in terms of 1000)
     ///
@@ -201,8 +426,8 @@ impl Gacha {
     ///
     /// # Examples
     /// ```
-    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
-    /// let gacha = GachaBuilder::new(79.0, 18.5, 2.5)
+    /// # use blue_gacha::gacha::{Gacha, GachaBuilder, Rarity};
+    /// let gacha: Gacha = GachaBuilder::new(79.0, 18.5, 2.5)
     ///     .with_pool(Vec::new())
     ///     .finish()
     ///     .unwrap();
@@ -210,10 +435,243 @@ impl Gacha {
     /// assert_eq!(gacha.get_rate(Rarity::One), 790);
     /// ```
     pub fn get_rate(&self, rarity: Rarity) -> usize {
-        match rarity {
-            Rarity::One => self.rates.0,
-            Rarity::Two => self.rates.1,
-            Rarity::Three => self.rates.2,
+        self.tiers.rate(rarity)
+    }
+
+    /// Returns the Tier an Item belongs to, which is how its Rarity should be
+    /// shown
+    ///
+    /// # Arguments
+    /// * `student` - The Item whose Tier will be returned
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{Gacha, GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// # use blue_gacha::tier::{Tier, TierSet};
+    /// let tiers = TierSet::new(vec![
+    ///     Tier::new("R", "R", 78.5),
+    ///     Tier::new("SR", "SR", 18.5),
+    ///     Tier::new("SSR", "SSR", 3.0),
+    /// ])
+    /// .unwrap();
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let gacha: Gacha = GachaBuilder::default()
+    ///     .with_tiers(tiers)
+    ///     .with_pool(vec![hoshino.clone()])
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(gacha.tier_of(&hoshino).unwrap().to_string(), "SSR");
+    /// ```
+    pub fn tier_of(&self, student: &T) -> Option<&Tier> {
+        self.tiers.get(student.rarity())
+    }
+
+    /// Checks that every outcome of a pull on this Gacha has a Student to give
+    ///
+    /// Will return [`Error::InvalidBanner`] if a priority Student's Rarity
+    /// isn't one of the Gacha's Tiers, if the increased rates of a Tier add up
+    /// to more than the Tier's own rate, or if some of a Tier's rate is left
+    /// after its increased rates but every Student of the Tier has one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let pool = vec![
+    ///     hoshino.clone(),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    ///
+    /// let gacha = GachaBuilder::default().with_pool(pool.clone()).finish().unwrap();
+    /// assert!(gacha.validate().is_ok());
+    ///
+    /// // Nobody is left to pull with the rest of the 3★ rate
+    /// let gacha = GachaBuilder::default()
+    ///     .with_pool(pool)
+    ///     .with_priority(vec![hoshino.into_priority_student(0.7)])
+    ///     .finish()
+    ///     .unwrap();
+    /// assert!(gacha.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.priorities().iter().any(|priority_student| {
+            self.tiers
+                .get(priority_student.student().rarity())
+                .is_none()
+        }) {
+            return Err(Error::InvalidBanner(
+                "a priority student's rarity isn't one of the gacha's tiers",
+            ));
+        }
+
+        for rarity in self.tiers.rarities() {
+            let left = self
+                .get_rate(rarity)
+                .checked_sub(self.priority_rate(rarity))
+                .ok_or(Error::InvalidBanner(
+                    "increased rates add up to more than their tier's rate",
+                ))?;
+
+            let has_regular = self
+                .pool
+                .iter()
+                .any(|student| student.rarity() == rarity && !self.is_priority(student));
+            if left > 0 && !has_regular {
+                return Err(Error::InvalidBanner(
+                    "a tier with a rate has no students without increased rates",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn priorities(&self) -> &[PriorityStudent<T>] {
+        self.priority.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the sum of the increased rates of a Rarity (in terms of 1000)
+    fn priority_rate(&self, rarity: Rarity) -> usize {
+        self.priorities()
+            .iter()
+            .filter(|priority_student| priority_student.student().rarity() == rarity)
+            .map(|priority_student| priority_student.rate)
+            .sum()
+    }
+
+    fn is_priority(&self, student: &T) -> bool {
+        self.priorities()
+            .iter()
+            .any(|priority_student| priority_student.student().id() == student.id())
+    }
+
+    /// Exchanges a Ticket for a Student
+    ///
+    /// The Rarity of the Student is chosen using the Gacha's rates, limited to
+    /// the Rarities the Ticket allows. The Student is then chosen uniformly
+    /// from the pool; increased rates do not apply to Tickets.
+    ///
+    /// Will return `None` if the pool contains no Student the Ticket allows.
+    ///
+    /// # Arguments
+    /// * `ticket` - The Ticket to exchange
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity, Ticket};
+    /// # use blue_gacha::student::Student;
+    /// let mut hoshino = Student::new("ホシノ（水着）", Rarity::Three);
+    /// hoshino.limited = true;
+    /// let pool = vec![
+    ///     hoshino,
+    ///     Student::new("シロコ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    ///
+    /// let ticket = Ticket::new("★3確定募集チケット", Rarity::Three).permanent_only();
+    /// assert_eq!(gacha.draw_ticket(&ticket).unwrap().name, "シロコ");
+    ///
+    /// let ticket = Ticket::new("★2以上確定募集チケット", Rarity::Two);
+    /// assert!(gacha.draw_ticket(&ticket).unwrap().rarity >= Rarity::Two);
+    /// ```
+    pub fn draw_ticket(&self, ticket: &Ticket) -> Option<T> {
+        self.draw_ticket_with_rng(ticket, &mut rand::thread_rng())
+    }
+
+    /// Exchanges a Ticket for a Student using the given source of randomness,
+    /// see [`Gacha::draw_ticket`]
+    ///
+    /// # Arguments
+    /// * `ticket` - The Ticket to exchange
+    /// * `rng` - The source of randomness
+    pub fn draw_ticket_with_rng(&self, ticket: &Ticket, rng: &mut dyn RngCore) -> Option<T> {
+        let eligible = |student: &&T| {
+            student.rarity() >= ticket.min_rarity
+                && !(ticket.permanent_only && student.is_limited())
+        };
+
+        let rarities: Vec<Rarity> = self
+            .tiers
+            .rarities()
+            .filter(|&rarity| {
+                self.pool
+                    .iter()
+                    .filter(eligible)
+                    .any(|student| student.rarity() == rarity)
+            })
+            .collect();
+
+        let dist = WeightedIndex::new(rarities.iter().map(|&rarity| self.get_rate(rarity))).ok()?;
+        let rarity = rarities[dist.sample(rng)];
+
+        let students: Vec<&T> = self
+            .pool
+            .iter()
+            .filter(eligible)
+            .filter(|student| student.rarity() == rarity)
+            .collect();
+        let index = rng.gen_range(0..students.len());
+
+        Some(students[index].clone())
+    }
+
+    /// Returns the probability (from 0.0 to 1.0) of a single pull resulting
+    /// in the given Student
+    ///
+    /// Priority Students have their own rate. Every other Student shares
+    /// what remains of their Rarity's rate with their peers, which is 0.0 if
+    /// the increased rates take up the whole Rarity.
+    ///
+    /// # Arguments
+    /// * `student` - The Student who's pull rate will be returned
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::student::Student;
+    /// let aru = Student::new("アル", Rarity::Three);
+    /// let hina = Student::new("ヒナ", Rarity::Three);
+    /// let gacha = GachaBuilder::new(79.0, 18.5, 2.5)
+    ///     .with_pool(vec![aru.clone(), hina.clone()])
+    ///     .with_priority(vec![aru.clone().into_priority_student(0.7)])
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!((gacha.probability_of(&aru) - 0.007).abs() < 1e-9);
+    /// assert!((gacha.probability_of(&hina) - 0.018).abs() < 1e-9);
+    /// ```
+    pub fn probability_of(&self, student: &T) -> f64 {
+        if let Some(priority_student) = self
+            .priorities()
+            .iter()
+            .find(|priority_student| priority_student.student().id() == student.id())
+        {
+            return priority_student.rate as f64 / 1000.0;
+        }
+
+        if !self.pool.iter().any(|other| other.id() == student.id()) {
+            return 0.0;
+        }
+
+        let peers = self
+            .pool
+            .iter()
+            .filter(|other| other.rarity() == student.rarity() && !self.is_priority(other))
+            .count();
+
+        // Invalid Gachas (see `validate`) may have nothing left to share
+        match self
+            .get_rate(student.rarity())
+            .checked_sub(self.priority_rate(student.rarity()))
+        {
+            Some(left) if peers > 0 => left as f64 / 1000.0 / peers as f64,
+            _ => 0.0,
         }
     }
 }
@@ -27,7 +27,25 @@ impl PartialEq<&str> for I18nString {
 
 impl std::fmt::Display for I18nString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.translations.get(&Language::Japanese).unwrap().as_str())
+        self.fmt_localized(Language::Japanese, f)
+    }
+}
+
+impl Localize for I18nString {
+    /// Writes the translation for `language`, falling back to the Japanese
+    /// text if no such translation exists.
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let message = self
+            .translations
+            .get(&language)
+            .or_else(|| self.translations.get(&Language::Japanese))
+            .unwrap();
+
+        f.write_str(message.as_str())
     }
 }
 
@@ -62,15 +80,86 @@ impl I18nString {
     pub fn get(&self, language: Language) -> Option<String> {
         self.translations.get(&language).cloned()
     }
+
+    /// Get a Language's translation as a borrowed &str
+    ///
+    /// Will return None if there is no translation for the given language
+    pub fn translation(&self, language: Language) -> Option<&str> {
+        self.translations.get(&language).map(String::as_str)
+    }
+
+    /// Iterates over every available translation, in no particular order
+    pub fn translations(&self) -> impl Iterator<Item = (Language, &str)> {
+        self.translations
+            .iter()
+            .map(|(language, message)| (*language, message.as_str()))
+    }
 }
 
 /// This enum represents all Languages this Gacha Simulator **must** support.
 ///
 /// This enum follows ISO-639-2/T
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum Language {
     #[serde(rename = "eng")]
     English,
     #[serde(rename = "jpn")]
     Japanese,
 }
+
+impl Language {
+    /// Every Language this Gacha Simulator supports
+    pub const ALL: [Language; 2] = [Language::English, Language::Japanese];
+}
+
+/// Types which can be formatted in a specific [`Language`]
+///
+/// The [`std::fmt::Display`] implementations throughout this crate print
+/// Japanese, as that is the language Blue Archive was written in. Localize
+/// allows for switching the language of that output in one place.
+pub trait Localize {
+    /// Formats `self` in the given language
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result;
+
+    /// Returns a wrapper which implements [`std::fmt::Display`] in the given language
+    ///
+    /// # Arguments
+    /// * `language` - The Language `self` should be displayed in
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::i18n::{Language, Localize};
+    /// # use blue_gacha::student::Student;
+    /// let mut mutsuki = Student::new("ムツキ", Rarity::Two);
+    /// mutsuki.add_translation(Language::English, "Mutsuki");
+    ///
+    /// assert_eq!(mutsuki.display_in(Language::English).to_string(), "Mutsuki\t2-star");
+    /// assert_eq!(mutsuki.display_in(Language::Japanese).to_string(), "ムツキ\t2★");
+    /// ```
+    fn display_in(&self, language: Language) -> Localized<'_, Self> {
+        Localized {
+            inner: self,
+            language,
+        }
+    }
+}
+
+/// A [`Localize`] type paired with the [`Language`] it should be displayed in
+///
+/// Created by [`Localize::display_in`]
+#[derive(Debug, Clone, Copy)]
+pub struct Localized<'a, T: ?Sized> {
+    inner: &'a T,
+    language: Language,
+}
+
+impl<'a, T: Localize + ?Sized> std::fmt::Display for Localized<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt_localized(self.language, f)
+    }
+}
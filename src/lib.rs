@@ -1,4 +1,25 @@
+pub mod alias;
 pub mod banner;
+pub mod database;
+pub mod definition;
+pub mod error;
+pub mod fair;
 pub mod gacha;
 pub mod i18n;
+pub mod pity;
+pub mod planner;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod replay;
+pub mod rng;
+pub mod rules;
+pub mod sampler;
+pub mod schedule;
+pub mod search;
+pub mod selection;
+pub mod session;
+pub mod simulation;
+pub mod statistics;
+pub mod step_up;
 pub mod student;
+pub mod tier;
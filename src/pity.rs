@@ -0,0 +1,121 @@
+//! Pity systems, as used by gachas other than Blue Archive's
+//!
+//! Blue Archive itself has no pity beyond sparking, so Banners have no
+//! [`Pity`] unless one is added with [`crate::banner::BannerBuilder::with_pity`].
+
+/// The pity rules of a Banner. Pity only ever applies to the highest Rarity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pity {
+    /// The pull on which the highest Rarity is guaranteed, counting from the
+    /// last time it was pulled
+    pub hard: Option<u32>,
+    pub soft: Option<SoftPity>,
+    /// Whether pulling the highest Rarity is a 50/50 between the rate-up
+    /// Students and everyone else. Losing guarantees a rate-up Student next
+    /// time, even on a different Banner.
+    pub fifty_fifty: bool,
+}
+
+/// Increases the rate of the highest Rarity with every pull past a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftPity {
+    /// The last pull which uses the normal rate, counting from the last time
+    /// the highest Rarity was pulled
+    pub start: u32,
+    /// How much the rate increases with every pull after `start` (in terms of 1000)
+    pub increase: usize,
+}
+
+impl Pity {
+    /// Creates a new Pity without any rules
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Guarantees the highest Rarity on the given pull
+    ///
+    /// # Arguments
+    /// * `pulls` - The pull on which the highest Rarity is guaranteed
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::pity::Pity;
+    /// let pity = Pity::new().with_hard_pity(90);
+    /// ```
+    pub fn with_hard_pity(self, pulls: u32) -> Self {
+        Self {
+            hard: Some(pulls),
+            ..self
+        }
+    }
+
+    /// Increases the rate of the highest Rarity with every pull past `start`
+    ///
+    /// # Arguments
+    /// * `start` - The last pull which uses the normal rate
+    /// * `increase` - How much the rate increases with every pull (in terms of 1000)
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::pity::Pity;
+    /// let pity = Pity::new().with_soft_pity(73, 60).with_hard_pity(90);
+    /// ```
+    pub fn with_soft_pity(self, start: u32, increase: usize) -> Self {
+        Self {
+            soft: Some(SoftPity { start, increase }),
+            ..self
+        }
+    }
+
+    /// Makes the highest Rarity a 50/50 between rate-up Students and everyone else
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::pity::Pity;
+    /// let pity = Pity::new().with_hard_pity(90).with_fifty_fifty();
+    /// ```
+    pub fn with_fifty_fifty(self) -> Self {
+        Self {
+            fifty_fifty: true,
+            ..self
+        }
+    }
+
+    /// Returns the rate of the highest Rarity (in terms of 1000) on a given pull
+    ///
+    /// # Arguments
+    /// * `base` - The rate of the highest Rarity without pity
+    /// * `pull` - The pull, counting from the last time the highest Rarity was pulled
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::pity::Pity;
+    /// let pity = Pity::new().with_soft_pity(73, 60).with_hard_pity(90);
+    ///
+    /// assert_eq!(pity.top_rate(6, 73), 6);
+    /// assert_eq!(pity.top_rate(6, 75), 126);
+    /// assert_eq!(pity.top_rate(6, 90), 1000);
+    /// ```
+    pub fn top_rate(&self, base: usize, pull: u32) -> usize {
+        if self.hard.is_some_and(|hard| pull >= hard) {
+            return 1000;
+        }
+
+        match self.soft {
+            Some(soft) if pull > soft.start => {
+                (base + (pull - soft.start) as usize * soft.increase).min(1000)
+            }
+            _ => base,
+        }
+    }
+}
+
+/// The progress a player has made towards pity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PityState {
+    /// The amount of pulls since the highest Rarity was last pulled
+    pub pulls_since_top: u32,
+    /// Whether the 50/50 was lost, which guarantees a rate-up Student the
+    /// next time the highest Rarity is pulled
+    pub guaranteed_featured: bool,
+}
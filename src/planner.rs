@@ -0,0 +1,288 @@
+use crate::banner::{Banner, SPARK_THRESHOLD};
+use crate::i18n::I18nString;
+use crate::student::Student;
+use chrono::NaiveDate;
+
+/// The currency available to spend on pulls
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Budget {
+    /// Pyroxene available on the first day of the plan
+    pub pyroxene: u32,
+    /// Recruitment tickets available on the first day of the plan. Each
+    /// ticket is worth a single pull.
+    pub tickets: u32,
+    /// Pyroxene earned every day
+    pub daily_pyroxene: u32,
+}
+
+/// A Banner the player would like to pull on
+#[derive(Debug, Clone)]
+pub struct PlannedBanner<'a> {
+    pub banner: &'a Banner,
+    /// The last day the Banner can be pulled on
+    pub end: NaiveDate,
+    /// The Students the player wants from this Banner
+    pub targets: Vec<Student>,
+    /// Banners with a lower priority value are funded first
+    pub priority: u32,
+}
+
+/// Recommends how many pulls to spend on each of a set of upcoming Banners
+///
+/// Every Banner is funded (in order of priority) up to its spark threshold
+/// (or [`SPARK_THRESHOLD`] pulls, if it has none) or its purchase limit, as
+/// long as doing so never spends currency before it's earned. Banners with a
+/// purchase limit are funded in whole 10-pulls at their own price, and don't
+/// accept tickets (see [`Banner::accepts_tickets`]).
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::BannerBuilder;
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+/// # use blue_gacha::planner::{Budget, Planner};
+/// # use blue_gacha::student::Student;
+/// # use chrono::NaiveDate;
+/// let hoshino = Student::new("ホシノ", Rarity::Three);
+/// let gacha = GachaBuilder::new(79.0, 18.5, 2.5)
+///     .with_pool(vec![
+///         hoshino.clone(),
+///         Student::new("シロコ", Rarity::Three),
+///         Student::new("セリカ", Rarity::Two),
+///         Student::new("ハルカ", Rarity::One),
+///     ])
+///     .with_priority(vec![hoshino.clone().into_priority_student(0.7)])
+///     .finish()
+///     .unwrap();
+/// let banner = BannerBuilder::new("ホシノ ピックアップ募集")
+///     .with_gacha(gacha)
+///     .with_sparkable_students(vec![hoshino.clone()])
+///     .finish()
+///     .unwrap();
+///
+/// let start = NaiveDate::from_ymd_opt(2021, 2, 4).unwrap();
+/// let budget = Budget { pyroxene: 12_000, tickets: 10, daily_pyroxene: 100 };
+/// let plan = Planner::new(start, budget)
+///     .with_banner(&banner, NaiveDate::from_ymd_opt(2021, 2, 18).unwrap(), vec![hoshino], 0)
+///     .plan();
+///
+/// // 12,000 + 14 * 100 Pyroxene is 111 pulls, plus 10 tickets
+/// assert_eq!(plan.banners[0].pulls, 121);
+/// assert!(!plan.banners[0].spark_guaranteed);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Planner<'a> {
+    start: NaiveDate,
+    budget: Budget,
+    banners: Vec<PlannedBanner<'a>>,
+}
+
+/// The outcome of pulling a set amount of times on every planned Banner
+#[derive(Debug, Clone)]
+pub struct Plan {
+    /// One entry per planned Banner, in the order they end
+    pub banners: Vec<BannerPlan>,
+    /// Whether every Banner's pulls can be paid for by the time it ends, and
+    /// bought within its purchase limit
+    pub affordable: bool,
+    /// Pyroxene left over after the last Banner ends
+    pub leftover_pyroxene: i64,
+}
+
+/// How many pulls to spend on a single Banner, and what they're likely to achieve
+#[derive(Debug, Clone)]
+pub struct BannerPlan {
+    pub banner: I18nString,
+    pub pulls: u32,
+    pub tickets_used: u32,
+    pub pyroxene_spent: u32,
+    /// The chance of pulling at least one copy of each target Student
+    pub targets: Vec<(Student, f64)>,
+    /// Whether enough pulls are made to spark a Student
+    pub spark_guaranteed: bool,
+}
+
+impl<'a> Planner<'a> {
+    /// Creates a new Planner
+    ///
+    /// # Arguments
+    /// * `start` - The first day of the plan, on which `budget` is available
+    /// * `budget` - The currency available to spend on pulls
+    pub fn new(start: NaiveDate, budget: Budget) -> Self {
+        Self {
+            start,
+            budget,
+            banners: Vec::new(),
+        }
+    }
+
+    /// Adds a Banner to the plan
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    /// * `end` - The last day the Banner can be pulled on
+    /// * `targets` - The Students the player wants from this Banner
+    /// * `priority` - Banners with a lower priority value are funded first
+    pub fn with_banner(
+        mut self,
+        banner: &'a Banner,
+        end: NaiveDate,
+        targets: Vec<Student>,
+        priority: u32,
+    ) -> Self {
+        self.banners.push(PlannedBanner {
+            banner,
+            end,
+            targets,
+            priority,
+        });
+        self.banners.sort_by_key(|planned| planned.end);
+        self
+    }
+
+    /// Returns the recommended amount of pulls for every Banner
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::planner::{Budget, Planner};
+    /// # use blue_gacha::student::Student;
+    /// # use chrono::NaiveDate;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let beginner = BannerBuilder::beginner().with_gacha(gacha).finish().unwrap();
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2021, 2, 4).unwrap();
+    /// let budget = Budget { pyroxene: 2_000, tickets: 10, daily_pyroxene: 0 };
+    /// let plan = Planner::new(start, budget)
+    ///     .with_banner(&beginner, start, Vec::new(), 0)
+    ///     .plan();
+    ///
+    /// // Three discounted 10-pulls of 600 Pyroxene each, and no tickets
+    /// assert_eq!(plan.banners[0].pulls, 30);
+    /// assert_eq!(plan.banners[0].pyroxene_spent, 1_800);
+    /// assert_eq!(plan.banners[0].tickets_used, 0);
+    /// ```
+    pub fn plan(&self) -> Plan {
+        let mut pulls = vec![0; self.banners.len()];
+
+        let mut order: Vec<usize> = (0..self.banners.len()).collect();
+        order.sort_by_key(|&i| self.banners[i].priority);
+
+        for i in order {
+            let banner = self.banners[i].banner;
+            let step = if banner.accepts_tickets() { 1 } else { 10 };
+            let threshold = banner.spark_threshold().unwrap_or(SPARK_THRESHOLD);
+            let limit = banner
+                .purchases_remaining()
+                .map_or(threshold, |purchases| purchases * 10);
+
+            // Spending more on one Banner never makes another cheaper, so the
+            // most this Banner can be funded with is found by bisection
+            let (mut low, mut high) = (0, threshold.min(limit) / step);
+            while low < high {
+                let mid = (low + high + 1) / 2;
+                pulls[i] = mid * step;
+
+                if self.evaluate(&pulls).affordable {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+
+            pulls[i] = low * step;
+        }
+
+        self.evaluate(&pulls)
+    }
+
+    /// Evaluates a plan which spends the given amount of pulls on every
+    /// Banner, in the order the Banners end
+    ///
+    /// Tickets are spent on the earliest Banners which accept them. Pulls on
+    /// Banners with a purchase limit are bought as whole 10-pulls, rounding up.
+    ///
+    /// # Arguments
+    /// * `pulls` - The amount of pulls spent on each Banner
+    pub fn evaluate(&self, pulls: &[u32]) -> Plan {
+        let mut tickets = self.budget.tickets;
+        let mut pyroxene_spent: i64 = 0;
+        let mut affordable = true;
+
+        let banners = self
+            .banners
+            .iter()
+            .zip(pulls.iter().copied())
+            .map(|(planned, pulls)| {
+                let banner = planned.banner;
+                let (tickets_used, pyroxene) = if banner.accepts_tickets() {
+                    let tickets_used = tickets.min(pulls);
+                    let pyroxene = (pulls - tickets_used) * banner.ten_pull_cost() / 10;
+                    (tickets_used, pyroxene)
+                } else {
+                    let purchases = (pulls + 9) / 10;
+                    affordable &= banner
+                        .purchases_remaining()
+                        .map_or(true, |remaining| purchases <= remaining);
+                    (0, purchases * banner.ten_pull_cost())
+                };
+                tickets -= tickets_used;
+
+                pyroxene_spent += pyroxene as i64;
+                affordable &= pyroxene_spent <= self.pyroxene_available_by(planned.end);
+
+                // Banners without a spark threshold can't be sparked on at all
+                let sparked = planned
+                    .banner
+                    .spark_threshold()
+                    .is_some_and(|threshold| pulls >= threshold);
+                let spark_guaranteed = sparked && planned.banner.sparkable().is_some();
+
+                let targets = planned
+                    .targets
+                    .iter()
+                    .map(|target| {
+                        let chance = if sparked && planned.banner.is_sparkable(target) {
+                            1.0
+                        } else {
+                            let rate = planned.banner.gacha().probability_of(target);
+                            1.0 - (1.0 - rate).powi(pulls as i32)
+                        };
+
+                        (target.clone(), chance)
+                    })
+                    .collect();
+
+                BannerPlan {
+                    banner: planned.banner.name.clone(),
+                    pulls,
+                    tickets_used,
+                    pyroxene_spent: pyroxene,
+                    targets,
+                    spark_guaranteed,
+                }
+            })
+            .collect();
+
+        let last_day = self
+            .banners
+            .last()
+            .map_or(self.start, |planned| planned.end);
+
+        Plan {
+            banners,
+            affordable,
+            leftover_pyroxene: self.pyroxene_available_by(last_day) - pyroxene_spent,
+        }
+    }
+
+    fn pyroxene_available_by(&self, date: NaiveDate) -> i64 {
+        let days = (date - self.start).num_days().max(0);
+        self.budget.pyroxene as i64 + days * self.budget.daily_pyroxene as i64
+    }
+}
@@ -0,0 +1,442 @@
+//! A Python extension module, for using the gacha engine from notebooks
+//!
+//! Build it with [maturin](https://www.maturin.rs), e.g. `maturin develop --features python`,
+//! then `import blue_gacha`.
+//!
+//! Students, Banners and Gachas are Python classes, while every result (pulls,
+//! odds, simulations and goodness-of-fit tests) is returned as plain lists and
+//! dicts, shaped like the JSON the rest of this crate reads and writes.
+//!
+//! ```python
+//! import blue_gacha
+//!
+//! db = blue_gacha.StudentDatabase.from_json(open("students.json").read())
+//! gacha = blue_gacha.Gacha(db.students(), priority=[(db.find("ホシノ"), 0.7)])
+//! banner = blue_gacha.Banner("ピックアップ募集", gacha, seed=7)
+//!
+//! banner.roll10()  # [{"name": {"jpn": "ハルカ", ...}, "rarity": 1}, ...]
+//! banner.simulate(ten_pulls=20, accounts=1000, seed=7)["rarities"]
+//! ```
+// pyo3's macros convert every PyResult into a PyResult
+#![allow(clippy::useless_conversion)]
+
+use crate::banner::{Banner, BannerBuilder};
+use crate::database::StudentDatabase;
+use crate::definition::BannerDefinition;
+use crate::gacha::{Gacha, GachaBuilder, Item, Rarity};
+use crate::i18n::Language;
+use crate::pity::PityState;
+use crate::rng::{self, SeededRng};
+use crate::statistics::{self, GoodnessOfFit};
+use crate::student::Student;
+use crate::tier::TierSet;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Converts JSON into the equivalent Python object
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(value) => value.into_py(py),
+        Value::Number(number) => match number.as_u64() {
+            Some(number) => number.into_py(py),
+            None => number.as_f64().unwrap_or(f64::NAN).into_py(py),
+        },
+        Value::String(value) => value.into_py(py),
+        Value::Array(values) => {
+            let list = PyList::empty_bound(py);
+            for value in values {
+                list.append(to_python(py, value)?)?;
+            }
+            list.into_py(py)
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new_bound(py);
+            for (key, value) in map {
+                dict.set_item(key, to_python(py, value)?)?;
+            }
+            dict.into_py(py)
+        }
+    })
+}
+
+fn student_dict(py: Python<'_>, student: &Student) -> PyResult<PyObject> {
+    let value =
+        serde_json::to_value(student).map_err(|err| PyValueError::new_err(err.to_string()))?;
+    to_python(py, &value)
+}
+
+fn parse_language(code: &str) -> PyResult<Language> {
+    serde_json::from_value(Value::String(code.to_string()))
+        .map_err(|_| PyValueError::new_err(format!("unknown language {:?}", code)))
+}
+
+fn fit_dict(py: Python<'_>, fit: GoodnessOfFit) -> PyResult<PyObject> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("statistic", fit.statistic)?;
+    dict.set_item("degrees_of_freedom", fit.degrees_of_freedom)?;
+    dict.set_item("p_value", fit.p_value)?;
+    Ok(dict.into_py(py))
+}
+
+/// A Student, see [`Student`]
+#[pyclass(name = "Student", module = "blue_gacha")]
+#[derive(Clone)]
+struct PyStudent {
+    student: Student,
+}
+
+#[pymethods]
+impl PyStudent {
+    #[new]
+    #[pyo3(signature = (jpn_name, rarity, limited = false))]
+    fn new(jpn_name: &str, rarity: u8, limited: bool) -> Self {
+        let mut student = Student::new(jpn_name, Rarity(rarity));
+        student.limited = limited;
+        Self { student }
+    }
+
+    /// The Japanese name, which identifies the Student
+    #[getter]
+    fn name(&self) -> &str {
+        self.student.id()
+    }
+
+    #[getter]
+    fn rarity(&self) -> u8 {
+        self.student.rarity.0
+    }
+
+    #[getter]
+    fn limited(&self) -> bool {
+        self.student.limited
+    }
+
+    /// Returns the Student's name in a language such as "eng", if it has been translated
+    fn translation(&self, language: &str) -> PyResult<Option<String>> {
+        Ok(self.student.name.get(parse_language(language)?))
+    }
+
+    fn add_translation(&mut self, language: &str, name: &str) -> PyResult<()> {
+        self.student
+            .add_translation(parse_language(language)?, name);
+        Ok(())
+    }
+
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        student_dict(py, &self.student)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Student({:?}, {})",
+            self.student.id(),
+            self.student.rarity.0
+        )
+    }
+}
+
+/// A collection of Students, see [`StudentDatabase`]
+#[pyclass(name = "StudentDatabase", module = "blue_gacha")]
+struct PyStudentDatabase {
+    db: StudentDatabase,
+}
+
+#[pymethods]
+impl PyStudentDatabase {
+    #[new]
+    fn new(students: Vec<PyStudent>) -> Self {
+        Self {
+            db: StudentDatabase::new(students.into_iter().map(|s| s.student).collect()),
+        }
+    }
+
+    /// Loads a database from a JSON array of Students
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        StudentDatabase::from_json(json)
+            .map(|db| Self { db })
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    fn students(&self) -> Vec<PyStudent> {
+        wrap(self.db.students())
+    }
+
+    /// Finds a Student by their exact Japanese name
+    fn find(&self, jpn_name: &str) -> Option<PyStudent> {
+        self.db.find(jpn_name).map(|student| PyStudent {
+            student: student.clone(),
+        })
+    }
+
+    /// Finds Students whose name in any language matches the query
+    fn search(&self, query: &str) -> Vec<PyStudent> {
+        wrap(self.db.search(query))
+    }
+
+    fn __len__(&self) -> usize {
+        self.db.students().len()
+    }
+}
+
+fn wrap<'a>(students: impl IntoIterator<Item = &'a Student>) -> Vec<PyStudent> {
+    students
+        .into_iter()
+        .map(|student| PyStudent {
+            student: student.clone(),
+        })
+        .collect()
+}
+
+/// A pool of Students and their rates, see [`Gacha`]
+#[pyclass(name = "Gacha", module = "blue_gacha")]
+#[derive(Clone)]
+struct PyGacha {
+    gacha: Gacha,
+}
+
+#[pymethods]
+impl PyGacha {
+    /// `rates` are the percentages of each Rarity, and `priority` pairs
+    /// rate-up Students with their percentage
+    #[new]
+    #[pyo3(signature = (pool, rates = vec![79.0, 18.5, 2.5], priority = Vec::new()))]
+    fn new(
+        pool: Vec<PyStudent>,
+        rates: Vec<f32>,
+        priority: Vec<(PyStudent, f32)>,
+    ) -> PyResult<Self> {
+        let tiers = TierSet::blue_archive().with_rates(&rates).ok_or_else(|| {
+            PyValueError::new_err("rates must be one percentage per rarity, adding up to 100")
+        })?;
+
+        let gacha = GachaBuilder::default()
+            .with_tiers(tiers)
+            .with_pool(pool.into_iter().map(|s| s.student).collect())
+            .with_priority(
+                priority
+                    .into_iter()
+                    .map(|(s, rate)| s.student.into_priority_student(rate))
+                    .collect(),
+            )
+            .finish()
+            .ok_or_else(|| PyValueError::new_err("invalid pool or rates"))?;
+        gacha
+            .validate()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+        Ok(Self { gacha })
+    }
+
+    fn pool(&self) -> Vec<PyStudent> {
+        wrap(&self.gacha.pool)
+    }
+
+    /// Returns the probability of each Rarity, keyed by Rarity
+    fn rates(&self) -> BTreeMap<u8, f64> {
+        self.gacha
+            .tiers
+            .rarities()
+            .map(|rarity| (rarity.0, self.gacha.get_rate(rarity) as f64 / 1000.0))
+            .collect()
+    }
+
+    /// Returns the probability of pulling a Student with a single pull
+    fn probability_of(&self, student: &PyStudent) -> f64 {
+        self.gacha.probability_of(&student.student)
+    }
+}
+
+/// A Banner, along with its pity progress and a seeded source of randomness
+///
+/// Banners track their purchases, so they can't be shared between threads.
+#[pyclass(name = "Banner", module = "blue_gacha", unsendable)]
+struct PyBanner {
+    banner: Banner,
+    pity: PityState,
+    rng: SeededRng,
+}
+
+impl PyBanner {
+    fn from_banner(banner: Banner, seed: Option<u64>) -> Self {
+        Self {
+            banner,
+            pity: PityState::default(),
+            rng: rng::seeded(seed.unwrap_or_else(rand::random)),
+        }
+    }
+}
+
+#[pymethods]
+impl PyBanner {
+    /// Without a seed, pulls are seeded randomly
+    #[new]
+    #[pyo3(signature = (jpn_name, gacha, sparkable = Vec::new(), seed = None))]
+    fn new(
+        jpn_name: &str,
+        gacha: PyGacha,
+        sparkable: Vec<PyStudent>,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let mut builder = BannerBuilder::new(jpn_name).with_gacha(gacha.gacha);
+        if !sparkable.is_empty() {
+            builder =
+                builder.with_sparkable_students(sparkable.into_iter().map(|s| s.student).collect());
+        }
+
+        builder
+            .finish()
+            .map(|banner| Self::from_banner(banner, seed))
+            .ok_or_else(|| PyValueError::new_err("invalid banner"))
+    }
+
+    /// Builds a Banner from a JSON banner definition, looking its Students up in a database
+    #[staticmethod]
+    #[pyo3(signature = (database, json, seed = None))]
+    fn from_json(database: &PyStudentDatabase, json: &str, seed: Option<u64>) -> PyResult<Self> {
+        serde_json::from_str::<BannerDefinition>(json)
+            .map_err(crate::error::Error::from)
+            .and_then(|definition| definition.build(&database.db))
+            .map(|banner| Self::from_banner(banner, seed))
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// The Japanese name of the Banner
+    #[getter]
+    fn name(&self) -> String {
+        self.banner.name.to_string()
+    }
+
+    fn gacha(&self) -> PyGacha {
+        PyGacha {
+            gacha: self.banner.gacha().clone(),
+        }
+    }
+
+    /// Restarts the Banner's random stream from a seed
+    fn seed(&mut self, seed: u64) {
+        self.rng = rng::seeded(seed);
+    }
+
+    /// Performs a single pull, returning the pulled Student as a dict
+    fn roll(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        let student = self.banner.roll_ref_with_rng(&mut self.pity, &mut self.rng);
+        student_dict(py, student)
+    }
+
+    /// Performs a 10-pull, returning the pulled Students as a list of dicts
+    fn roll10(&mut self, py: Python<'_>) -> PyResult<Vec<PyObject>> {
+        let students = self
+            .banner
+            .try_roll10_ref_with_rng(&mut self.pity, &mut self.rng)
+            .ok_or_else(|| {
+                PyRuntimeError::new_err("the banner's purchase limit has been reached")
+            })?;
+
+        students
+            .iter()
+            .map(|student| student_dict(py, student))
+            .collect()
+    }
+
+    /// Returns the probability of pulling each Student with a single pull
+    fn odds(&self, py: Python<'_>) -> PyResult<Vec<PyObject>> {
+        let gacha = self.banner.gacha();
+        gacha
+            .pool
+            .iter()
+            .map(|student| {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("student", student_dict(py, student)?)?;
+                dict.set_item("probability", gacha.probability_of(student))?;
+                Ok(dict.into_py(py))
+            })
+            .collect()
+    }
+
+    /// Counts the results of many simulated accounts, see [`crate::simulation::Simulation`]
+    ///
+    /// Set exactly one of `pulls` or `ten_pulls`.
+    #[pyo3(signature = (pulls = None, ten_pulls = None, accounts = 1, seed = None, threads = None))]
+    fn simulate(
+        &self,
+        py: Python<'_>,
+        pulls: Option<u64>,
+        ten_pulls: Option<u64>,
+        accounts: u64,
+        seed: Option<u64>,
+        threads: Option<usize>,
+    ) -> PyResult<PyObject> {
+        let mut simulation = self.banner.simulate().with_accounts(accounts);
+        simulation = match (pulls, ten_pulls) {
+            (Some(pulls), None) => simulation.with_pulls(pulls),
+            (None, Some(ten_pulls)) => simulation.with_ten_pulls(ten_pulls),
+            _ => return Err(PyValueError::new_err("set one of pulls or ten_pulls")),
+        };
+        if let Some(seed) = seed {
+            simulation = simulation.with_seed(seed);
+        }
+        if let Some(threads) = threads {
+            simulation = simulation.with_threads(threads);
+        }
+
+        let histogram = simulation.run();
+        let students = PyDict::new_bound(py);
+        for (id, count) in histogram.students() {
+            students.set_item(id, count)?;
+        }
+        let rarities = PyDict::new_bound(py);
+        for (rarity, count) in histogram.rarities() {
+            rarities.set_item(rarity.0, count)?;
+        }
+
+        let dict = PyDict::new_bound(py);
+        dict.set_item("pulls", histogram.pulls())?;
+        dict.set_item("students", students)?;
+        dict.set_item("rarities", rarities)?;
+        Ok(dict.into_py(py))
+    }
+}
+
+/// Performs Pearson's chi-square test, see [`statistics::chi_square`]
+#[pyfunction]
+fn chi_square(
+    py: Python<'_>,
+    observed: Vec<u64>,
+    expected: Vec<f64>,
+) -> PyResult<Option<PyObject>> {
+    statistics::chi_square(&observed, &expected)
+        .map(|fit| fit_dict(py, fit))
+        .transpose()
+}
+
+/// Performs a G-test, see [`statistics::g_test`]
+#[pyfunction]
+fn g_test(py: Python<'_>, observed: Vec<u64>, expected: Vec<f64>) -> PyResult<Option<PyObject>> {
+    statistics::g_test(&observed, &expected)
+        .map(|fit| fit_dict(py, fit))
+        .transpose()
+}
+
+/// See [`statistics::chi_square_p_value`]
+#[pyfunction]
+fn chi_square_p_value(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    statistics::chi_square_p_value(statistic, degrees_of_freedom)
+}
+
+#[pymodule]
+fn blue_gacha(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("SAMPLING_VERSION", rng::SAMPLING_VERSION)?;
+    module.add_class::<PyStudent>()?;
+    module.add_class::<PyStudentDatabase>()?;
+    module.add_class::<PyGacha>()?;
+    module.add_class::<PyBanner>()?;
+    module.add_function(wrap_pyfunction!(chi_square, module)?)?;
+    module.add_function(wrap_pyfunction!(g_test, module)?)?;
+    module.add_function(wrap_pyfunction!(chi_square_p_value, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,369 @@
+//! Recording a Session as a compact log of actions, which can be replayed
+//! to reproduce every pull exactly
+//!
+//! A [`RecordedSession`] draws all of its randomness from a single seeded
+//! stream, so its [`ActionLog`] only needs the seed, the fingerprints of the
+//! Banners it was played on, and the actions taken.
+use crate::banner::Banner;
+use crate::gacha::{Item, Ticket};
+use crate::i18n::Language;
+use crate::rng::{self, SeededRng, SAMPLING_VERSION};
+use crate::session::{PullKind, Session};
+use crate::student::Student;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+
+/// Identifies everything about a Banner which affects its pulls, so that a
+/// log isn't replayed on a Banner which has since changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Returns the Fingerprint of a Banner's name, rates, pool, sparkable
+    /// Students, costs, limits and rules
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to fingerprint
+    pub fn of<T: Item>(banner: &Banner<T>) -> Self {
+        fn field(hasher: &mut Sha256, bytes: &[u8]) {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        }
+        fn number(hasher: &mut Sha256, number: Option<u64>) {
+            field(
+                hasher,
+                &number.map_or([0; 9], |number| {
+                    let mut bytes = [1; 9];
+                    bytes[1..].copy_from_slice(&number.to_le_bytes());
+                    bytes
+                }),
+            );
+        }
+
+        let mut hasher = Sha256::new();
+        let gacha = banner.gacha();
+        let empty_vec = Vec::new();
+        let priority = gacha.priority.as_ref().unwrap_or(&empty_vec);
+        let sparkable = banner.sparkable().unwrap_or_default();
+
+        let jpn_name = banner.name.translation(Language::Japanese);
+        field(&mut hasher, jpn_name.unwrap_or_default().as_bytes());
+
+        number(&mut hasher, Some(gacha.tiers.tiers().len() as u64));
+        for tier in gacha.tiers.tiers() {
+            number(&mut hasher, Some(tier.rate as u64));
+        }
+
+        number(&mut hasher, Some(gacha.pool.len() as u64));
+        for student in gacha.pool.iter() {
+            field(&mut hasher, student.id().as_bytes());
+            field(
+                &mut hasher,
+                &[student.rarity().0, student.is_limited() as u8],
+            );
+        }
+
+        number(&mut hasher, Some(priority.len() as u64));
+        for priority_student in priority {
+            field(&mut hasher, priority_student.student().id().as_bytes());
+            number(&mut hasher, Some(priority_student.rate as u64));
+        }
+
+        number(&mut hasher, Some(sparkable.len() as u64));
+        for student in sparkable {
+            field(&mut hasher, student.id().as_bytes());
+        }
+
+        number(&mut hasher, Some(banner.ten_pull_cost() as u64));
+        number(&mut hasher, banner.purchase_limit().map(u64::from));
+        number(
+            &mut hasher,
+            banner
+                .final_purchase_guarantee()
+                .map(|rarity| rarity.0 as u64),
+        );
+        number(&mut hasher, banner.spark_threshold().map(u64::from));
+        field(&mut hasher, banner.rules().fingerprint().as_bytes());
+
+        let digest = hasher.finalize();
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        Self(u64::from_le_bytes(bytes))
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl Serialize for Fingerprint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fingerprint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        u64::from_str_radix(&hex, 16).map(Self).map_err(|_| {
+            serde::de::Error::custom("expected a fingerprint of 16 hexadecimal digits")
+        })
+    }
+}
+
+/// A single action taken during a [`RecordedSession`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// A single pull on the current Banner
+    Pull,
+    /// A 10-pull on the current Banner
+    Pull10,
+    /// Sparks the Student with this Japanese name on the current Banner
+    Spark(String),
+    /// Exchanges a Ticket for a Student of the current Banner's Gacha
+    Ticket(Ticket),
+    /// Makes the Banner at this index the current Banner
+    Switch(usize),
+}
+
+/// Everything needed to replay a [`RecordedSession`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionLog {
+    /// The [`SAMPLING_VERSION`] the Session was recorded with
+    pub version: u32,
+    /// The seed of the Session's random stream
+    pub seed: u64,
+    /// The Fingerprints of the Banners the Session was played on, in order
+    pub banners: Vec<Fingerprint>,
+    /// Every action which succeeded, oldest first
+    pub actions: Vec<Action>,
+}
+
+/// A [`Session`] on a set of Banners which records every action it takes
+///
+/// Every 10-pull purchase is counted by the RecordedSession itself, from
+/// none, rather than by the Banners.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::BannerBuilder;
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+/// # use blue_gacha::replay::{replay, RecordedSession};
+/// # use blue_gacha::student::Student;
+/// let pool = vec![
+///     Student::new("ホシノ", Rarity::Three),
+///     Student::new("セリカ", Rarity::Two),
+///     Student::new("ハルカ", Rarity::One),
+/// ];
+/// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+/// let banners = vec![
+///     BannerBuilder::new("ピックアップ募集").with_gacha(gacha.clone()).finish().unwrap(),
+///     BannerBuilder::new("通常募集").with_gacha(gacha).finish().unwrap(),
+/// ];
+///
+/// let mut recorded = RecordedSession::new(&banners, 7).unwrap();
+/// recorded.roll10().unwrap();
+/// recorded.switch(1).unwrap();
+/// recorded.roll();
+///
+/// let (session, log) = recorded.finish();
+/// let replayed = replay(&banners, &log).unwrap();
+///
+/// let names = |session: &blue_gacha::session::Session| -> Vec<String> {
+///     session.history().iter().map(|record| record.student.name.to_string()).collect()
+/// };
+/// assert_eq!(names(&replayed), names(&session));
+/// ```
+pub struct RecordedSession<'a> {
+    banners: &'a [Banner],
+    current: usize,
+    purchases: Vec<u32>,
+    rng: SeededRng,
+    session: Session,
+    log: ActionLog,
+}
+
+impl<'a> RecordedSession<'a> {
+    /// Starts a Session on the first of the given Banners
+    ///
+    /// Will return `None` if there are no Banners.
+    ///
+    /// # Arguments
+    /// * `banners` - Every Banner the Session can switch to
+    /// * `seed` - The seed of the Session's random stream
+    pub fn new(banners: &'a [Banner], seed: u64) -> Option<Self> {
+        if banners.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            banners,
+            current: 0,
+            purchases: vec![0; banners.len()],
+            rng: rng::seeded(seed),
+            session: Session::new(),
+            log: ActionLog {
+                version: SAMPLING_VERSION,
+                seed,
+                banners: banners.iter().map(Fingerprint::of).collect(),
+                actions: Vec::new(),
+            },
+        })
+    }
+
+    /// Returns the Banner actions are currently taken on
+    pub fn banner(&self) -> &'a Banner {
+        &self.banners[self.current]
+    }
+
+    /// Makes another Banner the current Banner
+    ///
+    /// Will return `None` if there is no Banner at that index.
+    pub fn switch(&mut self, index: usize) -> Option<&'a Banner> {
+        self.act(Action::Switch(index))?;
+        Some(self.banner())
+    }
+
+    /// Performs a single pull on the current Banner
+    pub fn roll(&mut self) -> Student {
+        let students = self.act(Action::Pull).expect("a single pull can't fail");
+        students.into_iter().next().unwrap()
+    }
+
+    /// Performs a 10-pull on the current Banner
+    ///
+    /// Will return `None` if the Banner's purchase limit has been reached.
+    pub fn roll10(&mut self) -> Option<[Student; 10]> {
+        <[Student; 10]>::try_from(self.act(Action::Pull10)?).ok()
+    }
+
+    /// Sparks a Student on the current Banner, see [`Session::spark`]
+    pub fn spark(&mut self, jpn_name: &str) -> Option<Student> {
+        self.act(Action::Spark(jpn_name.to_string()))?.pop()
+    }
+
+    /// Exchanges a Ticket for a Student of the current Banner's Gacha, see
+    /// [`Session::use_ticket`]
+    pub fn use_ticket(&mut self, ticket: &Ticket) -> Option<Student> {
+        self.act(Action::Ticket(ticket.clone()))?.pop()
+    }
+
+    /// Returns the Session played so far
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Returns the log of every action taken so far
+    pub fn log(&self) -> &ActionLog {
+        &self.log
+    }
+
+    /// Ends the Session, returning it along with its log
+    pub fn finish(self) -> (Session, ActionLog) {
+        (self.session, self.log)
+    }
+
+    /// Takes an action, recording it if it succeeds
+    fn act(&mut self, action: Action) -> Option<Vec<Student>> {
+        let banner = self.banner();
+
+        let students = match &action {
+            Action::Pull => vec![self.session.roll_with_rng(banner, &mut self.rng)],
+            Action::Pull10 => {
+                let purchases = &mut self.purchases[self.current];
+                let students = banner
+                    .roller()
+                    .ten_pull(self.session.pity_mut(), *purchases, &mut self.rng)?
+                    .map(Student::clone);
+                *purchases += 1;
+                self.session.spend(banner.ten_pull_cost());
+                self.session.earn_spark_points(Some(banner), 10);
+
+                for student in students.iter() {
+                    self.session
+                        .record(&banner.name, student.clone(), PullKind::Ten);
+                }
+                students.to_vec()
+            }
+            Action::Spark(jpn_name) => vec![self.session.spark(banner, jpn_name)?],
+            Action::Ticket(ticket) => {
+                vec![self
+                    .session
+                    .use_ticket_with_rng(banner.gacha(), ticket, &mut self.rng)?]
+            }
+            Action::Switch(index) => {
+                if *index >= self.banners.len() {
+                    return None;
+                }
+                self.current = *index;
+                Vec::new()
+            }
+        };
+
+        self.log.actions.push(action);
+        Some(students)
+    }
+}
+
+/// The reasons an ActionLog can't be replayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The log was recorded with another version of the sampling procedure,
+    /// so it can't be replayed by this version of the crate
+    VersionMismatch(u32),
+    /// The Banner at this index is missing, or differs from the one the log
+    /// was recorded on
+    BannerMismatch(usize),
+    /// The action at this index failed, so the log wasn't recorded on these Banners
+    InvalidAction(usize),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::VersionMismatch(version) => write!(
+                f,
+                "the log was recorded with sampling version {}, not {}",
+                version, SAMPLING_VERSION
+            ),
+            ReplayError::BannerMismatch(index) => {
+                write!(f, "banner {} doesn't match the recorded banner", index)
+            }
+            ReplayError::InvalidAction(index) => write!(f, "action {} can't be replayed", index),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Replays every action of a log, reproducing the recorded Session
+///
+/// # Arguments
+/// * `banners` - The Banners the log was recorded on, in the same order
+/// * `log` - The log to replay
+pub fn replay(banners: &[Banner], log: &ActionLog) -> Result<Session, ReplayError> {
+    if log.version != SAMPLING_VERSION {
+        return Err(ReplayError::VersionMismatch(log.version));
+    }
+
+    for index in 0..banners.len().max(log.banners.len()) {
+        let recorded = log.banners.get(index);
+        if banners.get(index).map(Fingerprint::of).as_ref() != recorded {
+            return Err(ReplayError::BannerMismatch(index));
+        }
+    }
+
+    let mut session =
+        RecordedSession::new(banners, log.seed).ok_or(ReplayError::BannerMismatch(0))?;
+    for (index, action) in log.actions.iter().enumerate() {
+        session
+            .act(action.clone())
+            .ok_or(ReplayError::InvalidAction(index))?;
+    }
+
+    Ok(session.session)
+}
@@ -0,0 +1,45 @@
+//! The random number generator behind every seeded pull
+//!
+//! Seeded pulls give the same Students for the same seed and Banner on every
+//! platform and across releases of this crate, as long as [`SAMPLING_VERSION`]
+//! stays the same. Unseeded pulls use `rand::thread_rng`, whose algorithm may
+//! change with any release of `rand`.
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The name of the algorithm behind [`SeededRng`]
+pub const ALGORITHM: &str = "ChaCha8";
+
+/// The version of the procedure which turns a seed into pulls
+///
+/// This is bumped whenever a release makes the same seed and Banner give
+/// different pulls, such as a change to [`SeededRng`], to how a seed is
+/// expanded, or to the order and way random numbers are drawn while sampling.
+/// The golden vectors in `tests/golden_vectors.rs` are regenerated along with it.
+///
+/// Logs and transcripts which are replayed later record this version, see
+/// [`crate::replay::ActionLog`] and [`crate::fair::Transcript`].
+pub const SAMPLING_VERSION: u32 = 1;
+
+/// The RNG of every seeded pull: ChaCha with 8 rounds, as implemented by
+/// `rand_chacha`, which guarantees a portable, stable output
+pub type SeededRng = ChaCha8Rng;
+
+/// Creates the RNG of a seed
+///
+/// The 64-bit seed is expanded into ChaCha's 256-bit key with `rand_core`'s
+/// `seed_from_u64`, which fills the key using PCG32.
+///
+/// # Arguments
+/// * `seed` - The seed
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::rng::seeded;
+/// use rand::RngCore;
+///
+/// assert_eq!(seeded(7).next_u64(), seeded(7).next_u64());
+/// ```
+pub fn seeded(seed: u64) -> SeededRng {
+    SeededRng::seed_from_u64(seed)
+}
@@ -0,0 +1,214 @@
+use crate::banner::{PULL_COST, SPARK_THRESHOLD};
+use crate::gacha::{Item, Rarity};
+use crate::pity::{Pity, PityState};
+use crate::sampler::Sampler;
+use crate::student::Student;
+use rand::{Rng, RngCore};
+
+/// The rules of a gacha, which sit on top of a [`Sampler`]
+///
+/// A RuleSet decides what a pull costs, whether Students can be sparked, and
+/// which guarantees and pity apply. Every method but `fingerprint` has a
+/// default, so a RuleSet only needs to implement the rules which differ from a
+/// plain weighted pull.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::BannerBuilder;
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity, Recruitment};
+/// # use blue_gacha::rules::RuleSet;
+/// # use blue_gacha::sampler::Sampler;
+/// # use blue_gacha::student::Student;
+/// # use rand::RngCore;
+/// /// Every 10-pull contains at least one 3★ Student
+/// #[derive(Debug)]
+/// struct Generous;
+///
+/// impl RuleSet for Generous {
+///     fn fingerprint(&self) -> String {
+///         "generous".to_string()
+///     }
+///
+///     fn spark_threshold(&self) -> Option<u32> {
+///         None
+///     }
+///
+///     fn apply_ten_pull_guarantee<'s>(
+///         &self,
+///         sampler: &'s Sampler,
+///         students: &mut [&'s Student; 10],
+///         rng: &mut dyn RngCore,
+///     ) {
+///         if !students.iter().any(|student| student.rarity == Rarity::Three) {
+///             if let Some(student) = sampler.sample_rarity(Rarity::Three, rng) {
+///                 students[9] = student;
+///             }
+///         }
+///     }
+/// }
+///
+/// let pool = vec![Student::new("ホシノ", Rarity::Three), Student::new("ハルカ", Rarity::One)];
+/// let gacha = GachaBuilder::new(97.5, 0.0, 2.5).with_pool(pool).finish().unwrap();
+/// let banner = BannerBuilder::new("大盤振る舞い募集")
+///     .with_gacha(gacha)
+///     .with_rules(Generous)
+///     .finish()
+///     .unwrap();
+///
+/// assert!(banner.roll10().iter().any(|student| student.rarity == Rarity::Three));
+/// ```
+pub trait RuleSet<T: Item = Student>: std::fmt::Debug + Send + Sync {
+    /// Identifies these rules and every parameter which affects their pulls,
+    /// see [`crate::replay::Fingerprint`]
+    ///
+    /// Two RuleSets may only return the same fingerprint if they pull the
+    /// same way, and a RuleSet's fingerprint must not change between versions
+    /// unless its pulls do.
+    fn fingerprint(&self) -> String;
+
+    /// The amount of currency a 10-pull costs
+    fn ten_pull_cost(&self) -> u32 {
+        PULL_COST * 10
+    }
+
+    /// The amount of pulls needed to spark a Student, or `None` if Students
+    /// can't be sparked
+    fn spark_threshold(&self) -> Option<u32> {
+        Some(SPARK_THRESHOLD)
+    }
+
+    /// Returns true if every single pull is an independent draw from the
+    /// Sampler's rates, which lets [`crate::simulation`] count many pulls at once
+    ///
+    /// Only return true if `pull` isn't overridden.
+    fn independent_pulls(&self) -> bool {
+        false
+    }
+
+    /// Performs a single pull
+    ///
+    /// The pulled Student is borrowed from the Sampler, so nothing is cloned.
+    ///
+    /// # Arguments
+    /// * `sampler` - Samples Students using the Banner's rates
+    /// * `state` - The player's progress towards pity
+    /// * `rng` - The source of randomness
+    fn pull<'s>(
+        &self,
+        sampler: &'s Sampler<T>,
+        state: &mut PityState,
+        rng: &mut dyn RngCore,
+    ) -> &'s T {
+        let _ = state;
+        sampler.sample(rng)
+    }
+
+    /// Applies this RuleSet's guarantees to the result of a 10-pull
+    ///
+    /// # Arguments
+    /// * `sampler` - Samples Students using the Banner's rates
+    /// * `students` - The Students pulled, which may be replaced
+    /// * `rng` - The source of randomness
+    fn apply_ten_pull_guarantee<'s>(
+        &self,
+        sampler: &'s Sampler<T>,
+        students: &mut [&'s T; 10],
+        rng: &mut dyn RngCore,
+    ) {
+        let _ = (sampler, students, rng);
+    }
+}
+
+/// The rules of Blue Archive, which every Banner uses by default
+///
+/// * A 10-pull costs 1200 Pyroxene
+/// * Students can be sparked after 200 pulls
+/// * If a 10-pull contains no 2★ Student, and its last Student isn't a 3★,
+///   the last Student is replaced with a 2★
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlueArchive;
+
+impl<T: Item> RuleSet<T> for BlueArchive {
+    fn fingerprint(&self) -> String {
+        "blue_archive".to_string()
+    }
+
+    fn independent_pulls(&self) -> bool {
+        true
+    }
+
+    fn apply_ten_pull_guarantee<'s>(
+        &self,
+        sampler: &'s Sampler<T>,
+        students: &mut [&'s T; 10],
+        rng: &mut dyn RngCore,
+    ) {
+        let two_star_present = students
+            .iter()
+            .any(|student| student.rarity() == Rarity::Two);
+
+        if !two_star_present && students[students.len() - 1].rarity() != Rarity::Three {
+            if let Some(student) = sampler.sample_rarity(Rarity::Two, rng) {
+                students[students.len() - 1] = student;
+            }
+        }
+    }
+}
+
+/// Pity rules for gachas other than Blue Archive, see [`crate::pity`]
+///
+/// Banners with these rules have no spark, and no 10-pull guarantee.
+impl<T: Item> RuleSet<T> for Pity {
+    fn fingerprint(&self) -> String {
+        let hard = self
+            .hard
+            .map_or_else(|| "none".to_string(), |hard| hard.to_string());
+        let soft = self.soft.map_or_else(
+            || "none".to_string(),
+            |soft| format!("{}+{}", soft.start, soft.increase),
+        );
+
+        format!(
+            "pity;hard={};soft={};fifty_fifty={}",
+            hard, soft, self.fifty_fifty
+        )
+    }
+
+    fn spark_threshold(&self) -> Option<u32> {
+        None
+    }
+
+    fn pull<'s>(
+        &self,
+        sampler: &'s Sampler<T>,
+        state: &mut PityState,
+        rng: &mut dyn RngCore,
+    ) -> &'s T {
+        let top = sampler.gacha().tiers.top();
+        let rate = self.top_rate(sampler.gacha().get_rate(top), state.pulls_since_top + 1);
+
+        if rng.gen_range(0..1000) < rate {
+            let featured = if self.fifty_fifty && sampler.has_top_priority() {
+                Some(state.guaranteed_featured || rng.gen_bool(0.5))
+            } else {
+                None
+            };
+
+            if let Some(student) = sampler.sample_top(featured, rng) {
+                if let Some(won) = featured {
+                    state.guaranteed_featured = !won;
+                }
+                state.pulls_since_top = 0;
+
+                return student;
+            }
+        }
+
+        // Gachas without a top Tier Student, or with nothing below it, fall
+        // back to a plain pull
+        state.pulls_since_top += 1;
+        sampler
+            .sample_below(top, rng)
+            .unwrap_or_else(|| sampler.sample(rng))
+    }
+}
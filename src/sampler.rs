@@ -0,0 +1,390 @@
+use crate::alias::AliasTable;
+use crate::gacha::{Gacha, Item, Rarity};
+use crate::student::Student;
+use rand::Rng;
+use rand_distr::{Binomial, Distribution};
+
+/// A single outcome of a weighted pick
+#[derive(Debug, Clone, Copy)]
+enum Outcome {
+    /// Any Student of a Tier without an increased rate
+    Tier(Rarity),
+    /// The priority Student at this index
+    Priority(usize),
+}
+
+/// An alias table along with the outcome each of its entries stands for
+#[derive(Debug, Clone)]
+struct OutcomeTable {
+    alias: AliasTable,
+    outcomes: Vec<Outcome>,
+    weights: Vec<usize>,
+}
+
+impl OutcomeTable {
+    fn new(items: Vec<(Outcome, usize)>) -> Option<Self> {
+        let weights: Vec<usize> = items.iter().map(|item| item.1).collect();
+
+        Some(Self {
+            alias: AliasTable::new(&weights)?,
+            outcomes: items.into_iter().map(|item| item.0).collect(),
+            weights,
+        })
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Outcome {
+        self.outcomes[self.alias.sample(rng)]
+    }
+}
+
+/// The weighted sampling engine behind every Banner
+///
+/// A Sampler only knows how to pick Students from a [`Gacha`] according to
+/// its rates. Guarantees, pity and spark are left to a
+/// [`RuleSet`](crate::rules::RuleSet).
+///
+/// Every table a pull needs is built when the Sampler is created, so a pull
+/// takes constant time and doesn't allocate.
+#[derive(Debug, Clone)]
+pub struct Sampler<T = Student> {
+    gacha: Gacha<T>,
+    /// Picks a Tier or a priority Student using the Gacha's rates
+    outcomes: OutcomeTable,
+    /// Picks a top Tier Student, including priority Students
+    top: Option<OutcomeTable>,
+    /// Picks one of the top Tier priority Students
+    top_featured: Option<OutcomeTable>,
+    /// Picks a Student below Rarity `i + 1` at index `i`, including priority Students
+    below: Vec<Option<OutcomeTable>>,
+    /// Pool indices of every Student, by Rarity
+    by_rarity: Vec<Vec<usize>>,
+    /// Pool indices of every Student without an increased rate, by Rarity
+    regular: Vec<Vec<usize>>,
+}
+
+impl<T: Item> Sampler<T> {
+    /// Creates a new Sampler, building its sampling tables
+    ///
+    /// # Arguments
+    /// * `gacha` - The Gacha to sample Students from
+    ///
+    /// A Gacha which fails [`Gacha::validate`] can be sampled from, but may
+    /// panic when a pull lands on a Tier without Students.
+    ///
+    /// # Panics
+    /// Panics if every rate of the Gacha is 0
+    pub fn new(gacha: Gacha<T>) -> Self {
+        let empty_vec = Vec::new();
+        let priority_students = gacha.priority.as_ref().unwrap_or(&empty_vec);
+
+        let rarities = gacha
+            .pool
+            .iter()
+            .map(|student| student.rarity())
+            .chain(std::iter::once(gacha.tiers.top()))
+            .max()
+            .map_or(0, |rarity| rarity.0 as usize + 1);
+        let mut by_rarity = vec![Vec::new(); rarities];
+        let mut regular = vec![Vec::new(); rarities];
+
+        for (index, student) in gacha.pool.iter().enumerate() {
+            by_rarity[student.rarity().0 as usize].push(index);
+
+            // Rate-Up Units are only pulled through their own rate, which is
+            // taken out of their Tier's rate, see Gacha::probability_of
+            if !priority_students
+                .iter()
+                .any(|priority_student| student.id() == priority_student.student().id())
+            {
+                regular[student.rarity().0 as usize].push(index);
+            }
+        }
+
+        let priority_rate = |rarity: Rarity| -> usize {
+            priority_students
+                .iter()
+                .filter(|priority_student| priority_student.student().rarity() == rarity)
+                .map(|priority_student| priority_student.rate)
+                .sum()
+        };
+        let priority_items = |rarity: Option<Rarity>| -> Vec<(Outcome, usize)> {
+            priority_students
+                .iter()
+                .enumerate()
+                .filter(|(_, priority_student)| {
+                    rarity.map_or(true, |rarity| priority_student.student().rarity() == rarity)
+                })
+                .map(|(index, priority_student)| (Outcome::Priority(index), priority_student.rate))
+                .collect()
+        };
+
+        let mut items: Vec<(Outcome, usize)> = gacha
+            .tiers
+            .rarities()
+            .map(|rarity| {
+                (
+                    Outcome::Tier(rarity),
+                    gacha.get_rate(rarity).saturating_sub(priority_rate(rarity)),
+                )
+            })
+            .collect();
+        items.extend(priority_items(None));
+
+        let rarity_of = |outcome: Outcome| match outcome {
+            Outcome::Tier(rarity) => rarity,
+            Outcome::Priority(index) => priority_students[index].student().rarity(),
+        };
+        let below = gacha
+            .tiers
+            .rarities()
+            .map(|rarity| {
+                OutcomeTable::new(
+                    items
+                        .iter()
+                        .copied()
+                        .filter(|item| rarity_of(item.0) < rarity)
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let outcomes = OutcomeTable::new(items).expect("a Gacha's rates can't all be 0");
+
+        let top = gacha.tiers.top();
+        let mut top_items = Vec::new();
+        if !regular[top.0 as usize].is_empty() {
+            top_items.push((
+                Outcome::Tier(top),
+                gacha.get_rate(top).saturating_sub(priority_rate(top)),
+            ));
+        }
+        top_items.extend(priority_items(Some(top)));
+
+        Self {
+            top: OutcomeTable::new(top_items),
+            top_featured: OutcomeTable::new(priority_items(Some(top))),
+            below,
+            outcomes,
+            by_rarity,
+            regular,
+            gacha,
+        }
+    }
+
+    /// Returns the Gacha this Sampler samples from
+    pub fn gacha(&self) -> &Gacha<T> {
+        &self.gacha
+    }
+
+    fn pick<R: Rng + ?Sized>(&self, indices: &[usize], rng: &mut R) -> &T {
+        &self.gacha.pool[indices[rng.gen_range(0..indices.len())]]
+    }
+
+    fn members(table: &[Vec<usize>], rarity: Rarity) -> &[usize] {
+        table.get(rarity.0 as usize).map_or(&[], Vec::as_slice)
+    }
+
+    fn resolve<R: Rng + ?Sized>(&self, outcome: Outcome, rng: &mut R) -> &T {
+        match outcome {
+            Outcome::Priority(index) => self.gacha.priority.as_ref().unwrap()[index].student(),
+            Outcome::Tier(rarity) => self.pick(Self::members(&self.regular, rarity), rng),
+        }
+    }
+
+    /// Picks a Student using the Gacha's rates, including increased rates
+    ///
+    /// The Student is borrowed from the Gacha's pool, so nothing is cloned.
+    ///
+    /// # Arguments
+    /// * `rng` - The source of randomness
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::sampler::Sampler;
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let sampler = Sampler::new(GachaBuilder::default().with_pool(pool).finish().unwrap());
+    ///
+    /// let student = sampler.sample(&mut rand::thread_rng());
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> &T {
+        self.resolve(self.outcomes.sample(rng), rng)
+    }
+
+    /// Performs many pulls at once, calling `count` with every Student pulled
+    /// and how many times they were pulled
+    ///
+    /// The counts follow the same multinomial distribution as calling
+    /// [`Sampler::sample`] `pulls` times, but take time proportional to the
+    /// size of the pool rather than to `pulls`.
+    ///
+    /// # Arguments
+    /// * `pulls` - The amount of pulls
+    /// * `rng` - The source of randomness
+    /// * `count` - Called with each pulled Student and their amount of copies
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::sampler::Sampler;
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let sampler = Sampler::new(GachaBuilder::default().with_pool(pool).finish().unwrap());
+    ///
+    /// let mut total = 0;
+    /// sampler.sample_counts(1_000_000, &mut rand::thread_rng(), |_, copies| total += copies);
+    /// assert_eq!(total, 1_000_000);
+    /// ```
+    pub fn sample_counts<'s, R, F>(&'s self, pulls: u64, rng: &mut R, mut count: F)
+    where
+        R: Rng + ?Sized,
+        F: FnMut(&'s T, u64),
+    {
+        let table = &self.outcomes;
+        let mut remaining = pulls;
+        let mut remaining_weight: u64 = table.weights.iter().map(|&weight| weight as u64).sum();
+
+        for (&outcome, &weight) in table.outcomes.iter().zip(&table.weights) {
+            if remaining == 0 {
+                break;
+            }
+
+            let copies = binomial(remaining, weight as u64, remaining_weight, rng);
+            remaining -= copies;
+            remaining_weight -= weight as u64;
+
+            if copies == 0 {
+                continue;
+            }
+
+            match outcome {
+                Outcome::Priority(index) => count(
+                    self.gacha.priority.as_ref().unwrap()[index].student(),
+                    copies,
+                ),
+                Outcome::Tier(rarity) => {
+                    let members = Self::members(&self.regular, rarity);
+                    assert!(
+                        !members.is_empty(),
+                        "there are no {} Students to pull",
+                        rarity
+                    );
+
+                    // Every Student of a Tier is equally likely
+                    let mut left = copies;
+                    for (i, &index) in members.iter().enumerate() {
+                        let copies = binomial(left, 1, (members.len() - i) as u64, rng);
+                        left -= copies;
+
+                        if copies > 0 {
+                            count(&self.gacha.pool[index], copies);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Picks a Student of the given Rarity, with every Student being equally likely
+    ///
+    /// Will return `None` if the pool has no Student of the Rarity.
+    ///
+    /// # Arguments
+    /// * `rarity` - The Rarity of the Student
+    /// * `rng` - The source of randomness
+    pub fn sample_rarity<R: Rng + ?Sized>(&self, rarity: Rarity, rng: &mut R) -> Option<&T> {
+        let members = Self::members(&self.by_rarity, rarity);
+        (!members.is_empty()).then(|| self.pick(members, rng))
+    }
+
+    /// Picks a Student below the given Rarity using the Gacha's rates
+    ///
+    /// Will return `None` if every Tier below the Rarity has a rate of 0.
+    ///
+    /// # Arguments
+    /// * `rarity` - The Student's Rarity will be lower than this
+    /// * `rng` - The source of randomness
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::sampler::Sampler;
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![Student::new("ホシノ", Rarity::Three), Student::new("ハルカ", Rarity::One)];
+    /// let gacha = GachaBuilder::new(97.5, 0.0, 2.5).with_pool(pool).finish().unwrap();
+    /// let sampler = Sampler::new(gacha);
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// assert_eq!(sampler.sample_below(Rarity::Three, &mut rng).unwrap().rarity, Rarity::One);
+    /// assert!(sampler.sample_below(Rarity::One, &mut rng).is_none());
+    /// ```
+    pub fn sample_below<R: Rng + ?Sized>(&self, rarity: Rarity, rng: &mut R) -> Option<&T> {
+        let table = match (rarity.0 as usize).checked_sub(1) {
+            None => None,
+            Some(index) => match self.below.get(index) {
+                Some(table) => table.as_ref(),
+                // Every Tier is below a Rarity above the top Tier
+                None => Some(&self.outcomes),
+            },
+        }?;
+
+        Some(self.resolve(table.sample(rng), rng))
+    }
+
+    /// Picks a Student of the Gacha's top Tier using the Gacha's rates
+    ///
+    /// `featured` limits the Student to the rate-up Students (`Some(true)`),
+    /// or to everyone else (`Some(false)`).
+    ///
+    /// Will return `None` if the pool has no top Tier Student.
+    ///
+    /// # Arguments
+    /// * `featured` - Which top Tier Students may be picked
+    /// * `rng` - The source of randomness
+    pub fn sample_top<R: Rng + ?Sized>(&self, featured: Option<bool>, rng: &mut R) -> Option<&T> {
+        let top = self.gacha.tiers.top();
+        let table = match featured {
+            None => self.top.as_ref(),
+            Some(true) => self.top_featured.as_ref(),
+            Some(false) => {
+                let others = Self::members(&self.regular, top);
+                if !others.is_empty() {
+                    return Some(self.pick(others, rng));
+                }
+                None
+            }
+        };
+
+        match table {
+            Some(table) => Some(self.resolve(table.sample(rng), rng)),
+            None => self.sample_rarity(top, rng),
+        }
+    }
+
+    /// Returns true if any Student of the Gacha's top Tier has an increased rate
+    pub fn has_top_priority(&self) -> bool {
+        self.top_featured.is_some()
+    }
+}
+
+/// Draws from a binomial distribution with a success chance of `weight / total`
+fn binomial<R: Rng + ?Sized>(trials: u64, weight: u64, total: u64, rng: &mut R) -> u64 {
+    if weight == 0 || trials == 0 {
+        0
+    } else if weight >= total {
+        trials
+    } else {
+        Binomial::new(trials, weight as f64 / total as f64)
+            .unwrap()
+            .sample(rng)
+    }
+}
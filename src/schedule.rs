@@ -0,0 +1,178 @@
+use crate::database::{StudentDatabase, StudentRef};
+use crate::definition::BannerDefinition;
+use crate::error::Error;
+use crate::student::Student;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// The Servers Blue Archive is released on. Banners often run on the
+/// Global server months after they did on the Japanese one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Server {
+    Japan,
+    Global,
+}
+
+/// A Banner which runs on a Server between two dates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledBanner {
+    pub banner: BannerDefinition,
+    pub server: Server,
+    /// The first day of the Banner
+    pub start: NaiveDate,
+    /// The last day of the Banner, inclusive
+    pub end: NaiveDate,
+    /// Whether this Banner is a rerun of an earlier one
+    #[serde(default)]
+    pub rerun: bool,
+}
+
+impl ScheduledBanner {
+    /// Returns true if the Banner is running on the given date
+    ///
+    /// # Arguments
+    /// * `date` - The date to check
+    pub fn is_live(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// A calendar of Banners, across every Server
+///
+/// A BannerSchedule serializes to and from a JSON Array of [`ScheduledBanner`]s.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::database::StudentDatabase;
+/// # use blue_gacha::gacha::Rarity;
+/// # use blue_gacha::schedule::{BannerSchedule, Server};
+/// # use blue_gacha::student::Student;
+/// # use chrono::NaiveDate;
+/// let db = StudentDatabase::new(vec![
+///     Student::new("ホシノ", Rarity::Three),
+///     Student::new("シロコ", Rarity::Three),
+///     Student::new("セリカ", Rarity::Two),
+///     Student::new("ハルカ", Rarity::One),
+/// ]);
+///
+/// let json = r#"[
+///     {
+///         "banner": {
+///             "name": { "translations": { "jpn": "ホシノ ピックアップ募集" } },
+///             "priority": [{ "student": "ホシノ", "rate": 0.7 }]
+///         },
+///         "server": "japan",
+///         "start": "2021-02-04",
+///         "end": "2021-02-18"
+///     },
+///     {
+///         "banner": {
+///             "name": { "translations": { "jpn": "シロコ ピックアップ募集" } },
+///             "priority": [{ "student": "シロコ", "rate": 0.7 }]
+///         },
+///         "server": "japan",
+///         "start": "2021-02-18",
+///         "end": "2021-03-04"
+///     }
+/// ]"#;
+/// let schedule = BannerSchedule::from_json(json).unwrap();
+/// let date = NaiveDate::from_ymd_opt(2021, 2, 18).unwrap();
+///
+/// assert_eq!(schedule.live_on(date, Server::Japan).len(), 2);
+/// assert!(schedule.live_on(date, Server::Global).is_empty());
+///
+/// let start = NaiveDate::from_ymd_opt(2021, 2, 1).unwrap();
+/// let next = schedule.next_appearance(&db, &"シロコ".into(), start, Server::Japan).unwrap();
+/// assert_eq!(next.start, date);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BannerSchedule {
+    banners: Vec<ScheduledBanner>,
+}
+
+impl BannerSchedule {
+    /// Creates a new BannerSchedule
+    ///
+    /// # Arguments
+    /// * `banners` - Every Banner in the schedule, in any order
+    pub fn new(banners: Vec<ScheduledBanner>) -> Self {
+        Self { banners }
+    }
+
+    /// Deserializes a BannerSchedule from a JSON Array of [`ScheduledBanner`]s
+    ///
+    /// # Arguments
+    /// * `json` - A JSON Array of ScheduledBanners
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Returns every Banner in the schedule
+    pub fn banners(&self) -> &[ScheduledBanner] {
+        &self.banners
+    }
+
+    /// Returns every Banner running on a Server on the given date
+    ///
+    /// # Arguments
+    /// * `date` - The date to check
+    /// * `server` - The Server to check
+    pub fn live_on(&self, date: NaiveDate, server: Server) -> Vec<&ScheduledBanner> {
+        self.banners
+            .iter()
+            .filter(|scheduled| scheduled.server == server && scheduled.is_live(date))
+            .collect()
+    }
+
+    /// Finds the earliest Banner on or after `date` in which a Student has
+    /// an increased rate. Banners which are already running count.
+    ///
+    /// # Arguments
+    /// * `db` - The database Students are looked up in
+    /// * `student` - The Student to look for
+    /// * `date` - The date to start looking from
+    /// * `server` - The Server to check
+    pub fn next_appearance(
+        &self,
+        db: &StudentDatabase,
+        student: &StudentRef,
+        date: NaiveDate,
+        server: Server,
+    ) -> Option<&ScheduledBanner> {
+        let student = db.resolve(student)?;
+
+        self.banners
+            .iter()
+            .filter(|scheduled| scheduled.server == server && scheduled.end >= date)
+            .filter(|scheduled| scheduled.banner.is_priority(db, student))
+            .min_by_key(|scheduled| scheduled.start)
+    }
+
+    /// Returns every Student who can be pulled from at least one Banner
+    /// running on a Server on the given date
+    ///
+    /// # Arguments
+    /// * `db` - The database Students are looked up in
+    /// * `date` - The date to check
+    /// * `server` - The Server to check
+    pub fn combined_pool(
+        &self,
+        db: &StudentDatabase,
+        date: NaiveDate,
+        server: Server,
+    ) -> Result<Vec<Student>, Error> {
+        let mut pool: Vec<Student> = Vec::new();
+
+        for scheduled in self.live_on(date, server) {
+            for student in scheduled.banner.pool(db)? {
+                if !pool.iter().any(|other| other.name == student.name) {
+                    pool.push(student);
+                }
+            }
+        }
+
+        Ok(pool)
+    }
+}
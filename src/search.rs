@@ -0,0 +1,204 @@
+//! Helpers used to match user-typed queries against Student names
+//!
+//! Queries are normalised before they're compared, which means that
+//! "hoshino", "ホシノ", "ﾎｼﾉ" and "ほしの" are all considered to be equal.
+
+const HALF_WIDTH_KATAKANA: &str = "ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝ";
+const FULL_WIDTH_KATAKANA: &str =
+    "ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+const HALF_WIDTH_DAKUTEN: char = 'ﾞ';
+const HALF_WIDTH_HANDAKUTEN: char = 'ﾟ';
+
+const DAKUTEN_PLAIN: &str = "ウカキクケコサシスセソタチツテトハヒフヘホ";
+const DAKUTEN_MARKED: &str = "ヴガギグゲゴザジズゼゾダヂヅデドバビブベボ";
+const HANDAKUTEN_PLAIN: &str = "ハヒフヘホ";
+const HANDAKUTEN_MARKED: &str = "パピプペポ";
+
+/// Katakana and their Hepburn romanization. Digraphs come first so that
+/// they take precedence over their individual characters.
+#[rustfmt::skip]
+const ROMAJI: &[(&str, &str)] = &[
+    ("キャ", "kya"), ("キュ", "kyu"), ("キョ", "kyo"),
+    ("シャ", "sha"), ("シュ", "shu"), ("ショ", "sho"), ("シェ", "she"),
+    ("チャ", "cha"), ("チュ", "chu"), ("チョ", "cho"), ("チェ", "che"),
+    ("ニャ", "nya"), ("ニュ", "nyu"), ("ニョ", "nyo"),
+    ("ヒャ", "hya"), ("ヒュ", "hyu"), ("ヒョ", "hyo"),
+    ("ミャ", "mya"), ("ミュ", "myu"), ("ミョ", "myo"),
+    ("リャ", "rya"), ("リュ", "ryu"), ("リョ", "ryo"),
+    ("ギャ", "gya"), ("ギュ", "gyu"), ("ギョ", "gyo"),
+    ("ジャ", "ja"), ("ジュ", "ju"), ("ジョ", "jo"), ("ジェ", "je"),
+    ("ビャ", "bya"), ("ビュ", "byu"), ("ビョ", "byo"),
+    ("ピャ", "pya"), ("ピュ", "pyu"), ("ピョ", "pyo"),
+    ("ティ", "ti"), ("ディ", "di"), ("トゥ", "tu"), ("ドゥ", "du"),
+    ("ファ", "fa"), ("フィ", "fi"), ("フェ", "fe"), ("フォ", "fo"),
+    ("ウィ", "wi"), ("ウェ", "we"), ("ウォ", "wo"), ("ヴァ", "va"),
+    ("ア", "a"), ("イ", "i"), ("ウ", "u"), ("エ", "e"), ("オ", "o"),
+    ("カ", "ka"), ("キ", "ki"), ("ク", "ku"), ("ケ", "ke"), ("コ", "ko"),
+    ("サ", "sa"), ("シ", "shi"), ("ス", "su"), ("セ", "se"), ("ソ", "so"),
+    ("タ", "ta"), ("チ", "chi"), ("ツ", "tsu"), ("テ", "te"), ("ト", "to"),
+    ("ナ", "na"), ("ニ", "ni"), ("ヌ", "nu"), ("ネ", "ne"), ("ノ", "no"),
+    ("ハ", "ha"), ("ヒ", "hi"), ("フ", "fu"), ("ヘ", "he"), ("ホ", "ho"),
+    ("マ", "ma"), ("ミ", "mi"), ("ム", "mu"), ("メ", "me"), ("モ", "mo"),
+    ("ヤ", "ya"), ("ユ", "yu"), ("ヨ", "yo"),
+    ("ラ", "ra"), ("リ", "ri"), ("ル", "ru"), ("レ", "re"), ("ロ", "ro"),
+    ("ワ", "wa"), ("ヲ", "o"), ("ン", "n"),
+    ("ガ", "ga"), ("ギ", "gi"), ("グ", "gu"), ("ゲ", "ge"), ("ゴ", "go"),
+    ("ザ", "za"), ("ジ", "ji"), ("ズ", "zu"), ("ゼ", "ze"), ("ゾ", "zo"),
+    ("ダ", "da"), ("ヂ", "ji"), ("ヅ", "zu"), ("デ", "de"), ("ド", "do"),
+    ("バ", "ba"), ("ビ", "bi"), ("ブ", "bu"), ("ベ", "be"), ("ボ", "bo"),
+    ("パ", "pa"), ("ピ", "pi"), ("プ", "pu"), ("ペ", "pe"), ("ポ", "po"),
+    ("ヴ", "vu"),
+    ("ァ", "a"), ("ィ", "i"), ("ゥ", "u"), ("ェ", "e"), ("ォ", "o"),
+    ("ャ", "ya"), ("ュ", "yu"), ("ョ", "yo"),
+];
+
+/// Normalises a name or query so that it can be compared against others
+///
+/// * Half-width katakana are converted to their full-width forms
+/// * Full-width ASCII is converted to ASCII
+/// * Hiragana are converted to katakana
+/// * Everything is lowercased
+/// * Whitespace and punctuation are removed
+///
+/// # Arguments
+/// * `text` - The text to normalise
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::search::normalize;
+/// assert_eq!(normalize("ﾎｼﾉ"), "ホシノ");
+/// assert_eq!(normalize("ほしの"), "ホシノ");
+/// assert_eq!(normalize("Hoshino (Swimsuit)"), "hoshinoswimsuit");
+/// assert_eq!(normalize("ｼﾞｭﾝｺ"), "ジュンコ");
+/// assert_eq!(normalize("ﾋﾟﾅ"), "ピナ");
+/// ```
+pub fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        let c = match c {
+            HALF_WIDTH_DAKUTEN | '\u{3099}' => {
+                voice(&mut normalized, DAKUTEN_PLAIN, DAKUTEN_MARKED);
+                continue;
+            }
+            HALF_WIDTH_HANDAKUTEN | '\u{309A}' => {
+                voice(&mut normalized, HANDAKUTEN_PLAIN, HANDAKUTEN_MARKED);
+                continue;
+            }
+            // Full-width ASCII
+            '\u{FF01}'..='\u{FF5E}' => std::char::from_u32(c as u32 - 0xFEE0).unwrap(),
+            // Hiragana
+            '\u{3041}'..='\u{3096}' => std::char::from_u32(c as u32 + 0x60).unwrap(),
+            _ => HALF_WIDTH_KATAKANA
+                .chars()
+                .position(|half| half == c)
+                .and_then(|i| FULL_WIDTH_KATAKANA.chars().nth(i))
+                .unwrap_or(c),
+        };
+
+        if c.is_alphanumeric() {
+            normalized.extend(c.to_lowercase());
+        }
+    }
+
+    normalized
+}
+
+/// Applies a dakuten or handakuten to the last character of `text`, if that
+/// character can take one
+///
+/// `plain` and `marked` are the characters before and after the mark is applied.
+fn voice(text: &mut String, plain: &str, marked: &str) {
+    let marked = text
+        .chars()
+        .last()
+        .and_then(|last| plain.chars().position(|c| c == last))
+        .and_then(|i| marked.chars().nth(i));
+
+    if let Some(marked) = marked {
+        text.pop();
+        text.push(marked);
+    }
+}
+
+/// Converts the katakana in a normalised string to Hepburn romaji
+///
+/// Characters which aren't katakana are left as is.
+///
+/// # Arguments
+/// * `normalized` - Text which has already been passed through [`normalize`]
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::search::{normalize, romanize};
+/// assert_eq!(romanize(&normalize("ホシノ")), "hoshino");
+/// assert_eq!(romanize(&normalize("チナツ")), "chinatsu");
+/// assert_eq!(romanize(&normalize("ハッカ")), "hakka");
+/// ```
+pub fn romanize(normalized: &str) -> String {
+    let mut romaji = String::with_capacity(normalized.len() * 2);
+    let mut rest = normalized;
+    let mut double_next = false;
+
+    while let Some(c) = rest.chars().next() {
+        if c == 'ッ' {
+            double_next = true;
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        if c == 'ー' {
+            if let Some(vowel) = romaji.chars().last() {
+                romaji.push(vowel);
+            }
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        match ROMAJI.iter().find(|(kana, _)| rest.starts_with(kana)) {
+            Some((kana, roman)) => {
+                if double_next {
+                    romaji.push_str(&roman[..1]);
+                }
+                romaji.push_str(roman);
+                rest = &rest[kana.len()..];
+            }
+            None => {
+                romaji.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        double_next = false;
+    }
+
+    romaji
+}
+
+/// Calculates the Levenshtein distance between two strings, in chars
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::search::edit_distance;
+/// assert_eq!(edit_distance("hoshino", "hoshina"), 1);
+/// assert_eq!(edit_distance("ホシノ", "ホシノ"), 0);
+/// ```
+pub fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    let mut current = vec![0; right.len() + 1];
+
+    for (i, l) in left.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, r) in right.iter().enumerate() {
+            let substitution = previous[j] + if l == *r { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[right.len()]
+}
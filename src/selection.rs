@@ -0,0 +1,346 @@
+use crate::banner::Banner;
+use crate::gacha::{Item, Rarity, Recruitment};
+use crate::i18n::I18nString;
+use crate::pity::PityState;
+use crate::session::{PullKind, Session};
+use crate::simulation::Simulation;
+use crate::student::Student;
+use rand::{Rng, RngCore};
+use std::cell::Cell;
+
+/// How the player obtains a Student from a SelectionBanner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// The player chooses any one of the Students for free
+    Reward,
+    /// The player pays for a single 10-pull, whose last Student is a 3★
+    /// drawn from the Students which can be chosen
+    Paid { cost: u32 },
+}
+
+/// Used to Construct a SelectionBanner
+#[derive(Debug)]
+pub struct SelectionBuilder {
+    banner: Banner,
+    choices: Vec<Student>,
+    mode: SelectionMode,
+}
+
+impl SelectionBuilder {
+    /// Creates a new instance of a SelectionBuilder
+    ///
+    /// By default, the SelectionBanner is a [`SelectionMode::Reward`].
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner every pull but the guaranteed one pulls from
+    pub fn new(banner: Banner) -> Self {
+        Self {
+            banner,
+            choices: Vec::new(),
+            mode: SelectionMode::Reward,
+        }
+    }
+
+    /// Sets the 3★ Students the player can obtain
+    ///
+    /// # Arguments
+    /// * `students` - A Vector of 3★ Students
+    pub fn with_choices(self, students: Vec<Student>) -> Self {
+        Self {
+            choices: students,
+            ..self
+        }
+    }
+
+    /// Makes the SelectionBanner a paid, one-time 10-pull
+    ///
+    /// # Arguments
+    /// * `cost` - The amount of Pyroxene the 10-pull costs
+    pub fn paid(self, cost: u32) -> Self {
+        Self {
+            mode: SelectionMode::Paid { cost },
+            ..self
+        }
+    }
+
+    /// Consumes a SelectionBuilder and returns a SelectionBanner
+    ///
+    /// Will return `None` if there are no choices, or if any of them aren't
+    /// 3★ or aren't in the Banner's pool.
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::selection::SelectionBuilder;
+    /// # use blue_gacha::student::Student;
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let shiroko = Student::new("シロコ", Rarity::Three);
+    /// let pool = vec![
+    ///     hoshino.clone(),
+    ///     shiroko.clone(),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("選択募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// let selection = SelectionBuilder::new(banner)
+    ///     .with_choices(vec![hoshino, shiroko])
+    ///     .paid(1200)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let students = selection.try_roll10().unwrap();
+    /// assert_eq!(students[9].rarity, Rarity::Three);
+    /// assert!(selection.try_roll10().is_none());
+    /// ```
+    pub fn finish(self) -> Option<SelectionBanner> {
+        let pool = &self.banner.gacha().pool;
+        if self.choices.is_empty()
+            || self.choices.iter().any(|student| {
+                student.rarity != Rarity::Three
+                    || !pool.iter().any(|other| other.id() == student.id())
+            })
+        {
+            return None;
+        }
+
+        Some(SelectionBanner {
+            banner: self.banner,
+            choices: self.choices,
+            mode: self.mode,
+            used: Cell::new(false),
+        })
+    }
+}
+
+/// A Banner which lets the player obtain one 3★ Student out of a limited
+/// selection, once
+#[derive(Debug, Clone)]
+pub struct SelectionBanner {
+    banner: Banner,
+    choices: Vec<Student>,
+    mode: SelectionMode,
+    used: Cell<bool>,
+}
+
+impl SelectionBanner {
+    /// The name of the Banner, which is used to identify it in a pull history
+    pub fn name(&self) -> &I18nString {
+        &self.banner.name
+    }
+
+    /// Returns the Students the player can obtain
+    pub fn choices(&self) -> &[Student] {
+        &self.choices
+    }
+
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Returns the amount of Pyroxene the 10-pull costs, or `None` if the
+    /// Banner is a [`SelectionMode::Reward`]
+    pub fn cost(&self) -> Option<u32> {
+        match self.mode {
+            SelectionMode::Reward => None,
+            SelectionMode::Paid { cost } => Some(cost),
+        }
+    }
+
+    /// Returns true if the player has already obtained their Student
+    pub fn is_used(&self) -> bool {
+        self.used.get()
+    }
+
+    /// Obtains the chosen Student from a [`SelectionMode::Reward`] Banner
+    ///
+    /// Will return `None` if the Banner is paid, has already been used, or
+    /// if the Student isn't one of the choices.
+    ///
+    /// # Arguments
+    /// * `jpn_name` - The Japanese name of the chosen Student
+    pub fn choose(&self, jpn_name: &str) -> Option<Student> {
+        if self.mode != SelectionMode::Reward || self.is_used() {
+            return None;
+        }
+
+        let student = self
+            .choices
+            .iter()
+            .find(|student| student.name == jpn_name)?
+            .clone();
+        self.used.set(true);
+        Some(student)
+    }
+
+    /// Performs the one-time 10-pull of a [`SelectionMode::Paid`] Banner
+    ///
+    /// Will return `None` if the Banner is a reward, or has already been used.
+    pub fn try_roll10(&self) -> Option<[Student; 10]> {
+        self.try_roll10_with_rng(&mut PityState::default(), &mut rand::thread_rng())
+    }
+
+    /// Performs the one-time 10-pull using the given source of randomness,
+    /// see [`SelectionBanner::try_roll10`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    /// * `rng` - The source of randomness
+    pub fn try_roll10_with_rng(
+        &self,
+        state: &mut PityState,
+        rng: &mut dyn RngCore,
+    ) -> Option<[Student; 10]> {
+        if self.mode == SelectionMode::Reward || self.is_used() {
+            return None;
+        }
+
+        let mut students = self.banner.try_roll10_ref_with_rng(state, rng)?;
+        students[students.len() - 1] = self.roll_choice(rng);
+        self.used.set(true);
+        Some(students.map(Student::clone))
+    }
+
+    /// Counts the results of many accounts making the one-time 10-pull of a
+    /// [`SelectionMode::Paid`] Banner, see [`Simulation`]
+    ///
+    /// Every account makes exactly one 10-pull, whatever amount of pulls is
+    /// set on the Simulation. Will return `None` if the Banner is a reward,
+    /// as its Student is chosen rather than pulled.
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::selection::SelectionBuilder;
+    /// # use blue_gacha::student::Student;
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let pool = vec![
+    ///     hoshino.clone(),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("選択募集").with_gacha(gacha).finish().unwrap();
+    /// let selection = SelectionBuilder::new(banner)
+    ///     .with_choices(vec![hoshino])
+    ///     .paid(1200)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let histogram = selection.simulate().unwrap().with_accounts(100).with_seed(7).run();
+    ///
+    /// assert_eq!(histogram.pulls(), 1_000);
+    /// assert!(histogram.count("ホシノ") >= 100);
+    /// ```
+    pub fn simulate(&self) -> Option<Simulation<'_, Student>> {
+        if self.mode == SelectionMode::Reward {
+            return None;
+        }
+
+        Some(
+            Simulation::new(&self.banner)
+                .with_ten_pulls(1)
+                .with_selection(&self.choices),
+        )
+    }
+
+    fn roll_choice(&self, rng: &mut dyn RngCore) -> &Student {
+        &self.choices[rng.gen_range(0..self.choices.len())]
+    }
+}
+
+impl Recruitment for SelectionBanner {
+    fn name(&self) -> &I18nString {
+        &self.banner.name
+    }
+
+    fn spark_banner(&self) -> Option<&Banner> {
+        Some(&self.banner)
+    }
+
+    /// SelectionBanners only offer their one 10-pull
+    ///
+    /// # Panics
+    /// Always panics
+    fn roll(&self) -> Student {
+        panic!("selection banners have no single pulls")
+    }
+
+    /// Performs the one-time 10-pull, see [`SelectionBanner::try_roll10`]
+    ///
+    /// # Panics
+    /// Panics if the Banner is a reward, or has already been used
+    fn roll10(&self) -> [Student; 10] {
+        self.try_roll10()
+            .expect("selection banner can't be pulled on")
+    }
+
+    fn try_roll10(&self) -> Option<[Student; 10]> {
+        SelectionBanner::try_roll10(self)
+    }
+
+    /// Performs the one-time 10-pull progressing towards pity, see
+    /// [`SelectionBanner::try_roll10`]
+    ///
+    /// # Panics
+    /// Panics if the Banner is a reward, or has already been used
+    fn roll10_with_pity(&self, state: &mut PityState) -> [Student; 10] {
+        self.try_roll10_with_pity(state)
+            .expect("selection banner can't be pulled on")
+    }
+
+    fn try_roll10_with_pity(&self, state: &mut PityState) -> Option<[Student; 10]> {
+        self.try_roll10_with_rng(state, &mut rand::thread_rng())
+    }
+
+    /// Returns the cost of the 10-pull, or 0 if the Banner is a reward
+    fn ten_pull_cost(&self) -> u32 {
+        self.cost().unwrap_or_default()
+    }
+}
+
+impl Session {
+    /// Obtains the chosen Student from a [`SelectionMode::Reward`] Banner and
+    /// records them, see [`SelectionBanner::choose`]
+    ///
+    /// # Arguments
+    /// * `banner` - The SelectionBanner
+    /// * `jpn_name` - The Japanese name of the chosen Student
+    pub fn choose(&mut self, banner: &SelectionBanner, jpn_name: &str) -> Option<Student> {
+        let student = banner.choose(jpn_name)?;
+        self.record(banner.name(), student.clone(), PullKind::Selection);
+        Some(student)
+    }
+
+    /// Performs the one-time 10-pull of a [`SelectionMode::Paid`] Banner and
+    /// records the results, see [`SelectionBanner::try_roll10`]
+    ///
+    /// # Arguments
+    /// * `banner` - The SelectionBanner
+    pub fn roll10_selection(&mut self, banner: &SelectionBanner) -> Option<[Student; 10]> {
+        self.roll10_selection_with_rng(banner, &mut rand::thread_rng())
+    }
+
+    /// Performs the one-time 10-pull of a [`SelectionMode::Paid`] Banner using
+    /// the given source of randomness, and records the results
+    ///
+    /// # Arguments
+    /// * `banner` - The SelectionBanner
+    /// * `rng` - The source of randomness
+    pub fn roll10_selection_with_rng(
+        &mut self,
+        banner: &SelectionBanner,
+        rng: &mut dyn RngCore,
+    ) -> Option<[Student; 10]> {
+        let students = banner.try_roll10_with_rng(self.pity_mut(), rng)?;
+        self.spend(banner.cost().unwrap_or_default());
+        self.earn_spark_points(Some(&banner.banner), 10);
+        for student in students.iter() {
+            self.record(banner.name(), student.clone(), PullKind::Ten);
+        }
+        Some(students)
+    }
+}
@@ -0,0 +1,422 @@
+use crate::banner::Banner;
+use crate::gacha::{Gacha, Recruitment, Ticket};
+use crate::i18n::I18nString;
+use crate::pity::PityState;
+use crate::replay::Fingerprint;
+use crate::student::Student;
+use rand::RngCore;
+use std::collections::HashMap;
+
+/// How a Student was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullKind {
+    Single,
+    Ten,
+    /// Chosen by the player, see [`crate::selection::SelectionBanner::choose`]
+    Selection,
+    /// Exchanged for spark points, see [`Session::spark`]
+    Spark,
+    /// Exchanged for a Ticket, see [`Session::use_ticket`]
+    Ticket,
+}
+
+/// A single Student obtained during a Session
+#[derive(Debug, Clone)]
+pub struct PullRecord {
+    /// The name of the Banner the Student was obtained from
+    pub banner: I18nString,
+    pub student: Student,
+    pub kind: PullKind,
+}
+
+/// A player's account over the course of a simulation, which keeps a
+/// history of every Student obtained
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::BannerBuilder;
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+/// # use blue_gacha::session::Session;
+/// # use blue_gacha::student::Student;
+/// let pool = vec![
+///     Student::new("ホシノ", Rarity::Three),
+///     Student::new("セリカ", Rarity::Two),
+///     Student::new("ハルカ", Rarity::One),
+/// ];
+/// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+/// let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+///
+/// let mut session = Session::new();
+/// session.roll(&banner);
+/// session.roll10(&banner);
+///
+/// assert_eq!(session.history().len(), 11);
+/// assert_eq!(session.pulls_on(&banner.name), 11);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    history: Vec<PullRecord>,
+    /// Spark points, keyed by the Fingerprint of the Banner they were earned
+    /// on, as linked Banners may share a name
+    spark_points: HashMap<Fingerprint, u32>,
+    /// Pity progress, which carries over between Banners
+    pity: PityState,
+    pyroxene_spent: u64,
+}
+
+impl Session {
+    /// Creates a new Session with an empty history
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Performs a single pull on a Banner and records the result
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    pub fn roll<B: Recruitment + ?Sized>(&mut self, banner: &B) -> Student {
+        let cost = banner.ten_pull_cost() / 10;
+        let student = banner.roll_with_pity(&mut self.pity);
+        self.spend(cost);
+        self.earn_spark_points(banner.spark_banner(), 1);
+        self.record(banner.name(), student.clone(), PullKind::Single);
+        student
+    }
+
+    /// Performs a 10-pull on a Banner and records the results
+    ///
+    /// Will return `None` if the Banner can't be pulled on anymore, such as
+    /// when its purchase limit has been reached.
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::{BannerBuilder, BEGINNER_PURCHASE_LIMIT};
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::session::Session;
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::beginner().with_gacha(gacha).finish().unwrap();
+    ///
+    /// let mut session = Session::new();
+    /// for _ in 0..BEGINNER_PURCHASE_LIMIT {
+    ///     assert!(session.roll10(&banner).is_some());
+    /// }
+    /// assert!(session.roll10(&banner).is_none());
+    /// ```
+    pub fn roll10<B: Recruitment + ?Sized>(&mut self, banner: &B) -> Option<[Student; 10]> {
+        let cost = banner.ten_pull_cost();
+        let students = banner.try_roll10_with_pity(&mut self.pity)?;
+        self.spend(cost);
+        self.earn_spark_points(banner.spark_banner(), 10);
+        for student in students.iter() {
+            self.record(banner.name(), student.clone(), PullKind::Ten);
+        }
+        Some(students)
+    }
+
+    /// Performs a single pull on a Banner using the given source of
+    /// randomness, and records the result
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    /// * `rng` - The source of randomness
+    pub fn roll_with_rng(&mut self, banner: &Banner, rng: &mut dyn RngCore) -> Student {
+        let student = banner.roll_ref_with_rng(&mut self.pity, rng).clone();
+        self.spend(banner.ten_pull_cost() / 10);
+        self.earn_spark_points(Some(banner), 1);
+        self.record(&banner.name, student.clone(), PullKind::Single);
+        student
+    }
+
+    /// Performs a 10-pull on a Banner using the given source of randomness,
+    /// and records the results
+    ///
+    /// Will return `None` if the Banner's purchase limit has been reached.
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    /// * `rng` - The source of randomness
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::session::Session;
+    /// # use blue_gacha::student::Student;
+    /// # use rand::SeedableRng;
+    /// # use rand_chacha::ChaCha8Rng;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// let mut session = Session::new();
+    /// let mut rng = ChaCha8Rng::seed_from_u64(7);
+    /// session.roll10_with_rng(&banner, &mut rng).unwrap();
+    ///
+    /// assert_eq!(session.spark_points(&banner), 10);
+    /// ```
+    pub fn roll10_with_rng(
+        &mut self,
+        banner: &Banner,
+        rng: &mut dyn RngCore,
+    ) -> Option<[Student; 10]> {
+        let students = banner
+            .try_roll10_ref_with_rng(&mut self.pity, rng)?
+            .map(Student::clone);
+        self.spend(banner.ten_pull_cost());
+        self.earn_spark_points(Some(banner), 10);
+        for student in students.iter() {
+            self.record(&banner.name, student.clone(), PullKind::Ten);
+        }
+        Some(students)
+    }
+
+    /// Adds a Student to the history, without earning any spark points
+    ///
+    /// # Arguments
+    /// * `banner` - The name of the Banner the Student was obtained from
+    /// * `student` - The Student who was obtained
+    /// * `kind` - How the Student was obtained
+    pub fn record(&mut self, banner: &I18nString, student: Student, kind: PullKind) {
+        self.history.push(PullRecord {
+            banner: banner.clone(),
+            student,
+            kind,
+        });
+    }
+
+    /// Returns the Pyroxene spent on pulls so far
+    ///
+    /// A 10-pull costs whatever the Banner charges for it at the time, such
+    /// as the current step of a [`crate::step_up::StepUpBanner`], and a single
+    /// pull costs a tenth of that.
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::session::Session;
+    /// # use blue_gacha::step_up::{Step, StepUpBuilder};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ステップアップ募集").with_gacha(gacha).finish().unwrap();
+    /// let step_up = StepUpBuilder::new(banner)
+    ///     .with_step(Step::new(600))
+    ///     .with_step(Step::new(1200))
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let mut session = Session::new();
+    /// session.roll10(&step_up);
+    /// session.roll10(&step_up);
+    /// assert!(session.roll10(&step_up).is_none());
+    ///
+    /// assert_eq!(session.pyroxene_spent(), 1_800);
+    /// ```
+    pub fn pyroxene_spent(&self) -> u64 {
+        self.pyroxene_spent
+    }
+
+    pub(crate) fn spend(&mut self, pyroxene: u32) {
+        self.pyroxene_spent += pyroxene as u64;
+    }
+
+    /// Returns the player's progress towards pity
+    pub fn pity(&self) -> PityState {
+        self.pity
+    }
+
+    pub(crate) fn pity_mut(&mut self) -> &mut PityState {
+        &mut self.pity
+    }
+
+    pub(crate) fn earn_spark_points(&mut self, banner: Option<&Banner>, points: u32) {
+        if let Some(banner) = banner {
+            *self
+                .spark_points
+                .entry(Fingerprint::of(banner))
+                .or_default() += points;
+        }
+    }
+
+    /// Returns the spark points earned on a Banner which haven't been spent yet
+    ///
+    /// Every pull on a Banner earns one point. Points can't be moved between
+    /// Banners, not even between the linked Banners of a double pick-up, and
+    /// Banners are told apart by their [`Fingerprint`] rather than their name.
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner, see [`Recruitment::spark_banner`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::session::Session;
+    /// # use blue_gacha::student::Student;
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let shiroko = Student::new("シロコ", Rarity::Three);
+    /// let pool = vec![
+    ///     hoshino.clone(),
+    ///     shiroko.clone(),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let hoshino_banner = BannerBuilder::new("ピックアップ募集")
+    ///     .with_gacha(gacha)
+    ///     .with_sparkable_students(vec![hoshino])
+    ///     .finish()
+    ///     .unwrap();
+    /// let shiroko_banner = hoshino_banner
+    ///     .link("ピックアップ募集", Vec::new(), vec![shiroko])
+    ///     .unwrap();
+    ///
+    /// let mut session = Session::new();
+    /// session.roll10(&hoshino_banner);
+    ///
+    /// assert_eq!(session.spark_points(&hoshino_banner), 10);
+    /// assert_eq!(session.spark_points(&shiroko_banner), 0);
+    /// ```
+    pub fn spark_points<B: Recruitment + ?Sized>(&self, banner: &B) -> u32 {
+        banner
+            .spark_banner()
+            .and_then(|banner| self.spark_points.get(&Fingerprint::of(banner)))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Exchanges spark points for one of the Banner's sparkable Students, and
+    /// records them
+    ///
+    /// Will return `None` if there aren't enough points, or if the Student
+    /// isn't sparkable on the Banner. The amount of points needed depends on
+    /// the Banner's [`RuleSet`](crate::rules::RuleSet).
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner the spark points were earned on
+    /// * `jpn_name` - The Japanese name of the Student to spark
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity};
+    /// # use blue_gacha::session::Session;
+    /// # use blue_gacha::student::Student;
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let pool = vec![hoshino.clone(), Student::new("セリカ", Rarity::Two), Student::new("ハルカ", Rarity::One)];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ピックアップ募集")
+    ///     .with_gacha(gacha)
+    ///     .with_sparkable_students(vec![hoshino])
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let mut session = Session::new();
+    /// assert!(session.spark(&banner, "ホシノ").is_none());
+    ///
+    /// for _ in 0..20 {
+    ///     session.roll10(&banner);
+    /// }
+    /// assert_eq!(session.spark_points(&banner), 200);
+    /// assert!(session.spark(&banner, "ホシノ").is_some());
+    /// assert_eq!(session.spark_points(&banner), 0);
+    /// ```
+    pub fn spark<B: Recruitment + ?Sized>(
+        &mut self,
+        banner: &B,
+        jpn_name: &str,
+    ) -> Option<Student> {
+        let spark_banner = banner.spark_banner()?;
+        let student = spark_banner
+            .sparkable()?
+            .iter()
+            .find(|student| student.name == jpn_name)?
+            .clone();
+
+        let threshold = spark_banner.spark_threshold()?;
+        let points = self.spark_points.get_mut(&Fingerprint::of(spark_banner))?;
+        if *points < threshold {
+            return None;
+        }
+        *points -= threshold;
+
+        self.record(banner.name(), student.clone(), PullKind::Spark);
+        Some(student)
+    }
+
+    /// Exchanges a Ticket for a Student, and records them under the Ticket's name
+    ///
+    /// Will return `None` if the Gacha contains no Student the Ticket allows,
+    /// see [`Gacha::draw_ticket`].
+    ///
+    /// # Arguments
+    /// * `gacha` - The Gacha the Ticket draws from
+    /// * `ticket` - The Ticket to exchange
+    pub fn use_ticket(&mut self, gacha: &Gacha, ticket: &Ticket) -> Option<Student> {
+        self.use_ticket_with_rng(gacha, ticket, &mut rand::thread_rng())
+    }
+
+    /// Exchanges a Ticket for a Student using the given source of randomness,
+    /// see [`Session::use_ticket`]
+    ///
+    /// # Arguments
+    /// * `gacha` - The Gacha the Ticket draws from
+    /// * `ticket` - The Ticket to exchange
+    /// * `rng` - The source of randomness
+    pub fn use_ticket_with_rng(
+        &mut self,
+        gacha: &Gacha,
+        ticket: &Ticket,
+        rng: &mut dyn RngCore,
+    ) -> Option<Student> {
+        let student = gacha.draw_ticket_with_rng(ticket, rng)?;
+        self.record(&ticket.name, student.clone(), PullKind::Ticket);
+        Some(student)
+    }
+
+    /// Returns every Student obtained during this Session, oldest first
+    pub fn history(&self) -> &[PullRecord] {
+        &self.history
+    }
+
+    /// Returns the amount of pulls made on a Banner. Chosen and sparked Students don't count.
+    ///
+    /// # Arguments
+    /// * `banner` - The name of the Banner
+    pub fn pulls_on(&self, banner: &I18nString) -> usize {
+        self.history
+            .iter()
+            .filter(|record| record.banner == *banner)
+            .filter(|record| matches!(record.kind, PullKind::Single | PullKind::Ten))
+            .count()
+    }
+
+    /// Returns how many copies of any version of a character were obtained
+    ///
+    /// # Arguments
+    /// * `base_jpn_name` - The Japanese name of the base character
+    pub fn copies_of(&self, base_jpn_name: &str) -> usize {
+        self.history
+            .iter()
+            .filter(|record| record.student.is_version_of(base_jpn_name))
+            .count()
+    }
+}
@@ -0,0 +1,289 @@
+use crate::banner::{Banner, Roller};
+use crate::gacha::{Item, Rarity};
+use crate::pity::PityState;
+use crate::rng;
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// How many copies of each Student and each Rarity were pulled
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Histogram {
+    pulls: u64,
+    students: HashMap<String, u64>,
+    rarities: BTreeMap<Rarity, u64>,
+}
+
+impl Histogram {
+    /// Returns the total amount of pulls counted
+    pub fn pulls(&self) -> u64 {
+        self.pulls
+    }
+
+    /// Returns how many copies of a Student were pulled
+    ///
+    /// # Arguments
+    /// * `id` - The [`Item::id`] of the Student
+    pub fn count(&self, id: &str) -> u64 {
+        self.students.get(id).copied().unwrap_or_default()
+    }
+
+    /// Returns every pulled Student's id, along with how many copies were pulled
+    pub fn students(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.students
+            .iter()
+            .map(|(id, &count)| (id.as_str(), count))
+    }
+
+    /// Returns how many Students of a Rarity were pulled
+    ///
+    /// # Arguments
+    /// * `rarity` - The Rarity to count
+    pub fn rarity_count(&self, rarity: Rarity) -> u64 {
+        self.rarities.get(&rarity).copied().unwrap_or_default()
+    }
+
+    /// Returns every pulled Rarity, from the most common Tier to the rarest,
+    /// along with how many Students of that Rarity were pulled
+    pub fn rarities(&self) -> impl Iterator<Item = (Rarity, u64)> + '_ {
+        self.rarities
+            .iter()
+            .map(|(&rarity, &count)| (rarity, count))
+    }
+
+    /// Adds the counts of another Histogram to this one
+    pub fn merge(&mut self, other: Histogram) {
+        self.pulls += other.pulls;
+
+        for (id, count) in other.students {
+            *self.students.entry(id).or_default() += count;
+        }
+
+        for (rarity, count) in other.rarities {
+            *self.rarities.entry(rarity).or_default() += count;
+        }
+    }
+}
+
+/// Counts pulled Students by address, which avoids hashing their ids on
+/// every pull. Pulled Students are borrowed from the same Sampler, so equal
+/// Students mostly share an address, and are merged by id at the end.
+struct Tally<'a, T> {
+    pulls: u64,
+    counts: HashMap<usize, (&'a T, u64)>,
+}
+
+impl<'a, T: Item> Tally<'a, T> {
+    fn new() -> Self {
+        Self {
+            pulls: 0,
+            counts: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, student: &'a T, copies: u64) {
+        self.pulls += copies;
+        self.counts
+            .entry(student as *const T as usize)
+            .or_insert((student, 0))
+            .1 += copies;
+    }
+
+    fn merge(&mut self, other: Tally<'a, T>) {
+        self.pulls += other.pulls;
+
+        for (address, (student, count)) in other.counts {
+            self.counts.entry(address).or_insert((student, 0)).1 += count;
+        }
+    }
+
+    fn into_histogram(self) -> Histogram {
+        let mut histogram = Histogram {
+            pulls: self.pulls,
+            ..Default::default()
+        };
+
+        for (student, count) in self.counts.into_values() {
+            *histogram
+                .students
+                .entry(student.id().to_string())
+                .or_default() += count;
+            *histogram.rarities.entry(student.rarity()).or_default() += count;
+        }
+
+        histogram
+    }
+}
+
+/// Simulates many accounts pulling on a Banner, counting what they pulled
+/// rather than keeping every result
+///
+/// Every account starts with no pity progress and no purchases, and pulls
+/// using its own random stream, derived from the seed and the account's
+/// index. Results for a given seed are the same however many threads are used.
+///
+/// If the Banner's rules make every single pull independent, single pulls are
+/// counted using a multinomial distribution instead of being pulled one by one.
+#[derive(Debug, Clone)]
+pub struct Simulation<'a, T: Item> {
+    banner: &'a Banner<T>,
+    accounts: u64,
+    pulls: u64,
+    ten_pulls: bool,
+    threads: usize,
+    seed: u64,
+    /// The Students a selection's one 10-pull ends with, see
+    /// [`crate::selection::SelectionBanner::simulate`]
+    selection: Option<&'a [T]>,
+}
+
+impl<'a, T: Item> Simulation<'a, T> {
+    /// Creates a new Simulation of one account doing no pulls
+    ///
+    /// Uses every available thread and a random seed by default.
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner to pull on
+    pub fn new(banner: &'a Banner<T>) -> Self {
+        Self {
+            banner,
+            accounts: 1,
+            pulls: 0,
+            ten_pulls: false,
+            threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            seed: rand::random(),
+            selection: None,
+        }
+    }
+
+    /// Makes each account do a single 10-pull whose last Student is one of
+    /// `choices`, whatever amount of pulls is set
+    pub(crate) fn with_selection(self, choices: &'a [T]) -> Self {
+        Self {
+            selection: Some(choices),
+            ..self
+        }
+    }
+
+    /// Makes each account do this many single pulls
+    ///
+    /// # Arguments
+    /// * `pulls` - The amount of single pulls per account
+    pub fn with_pulls(self, pulls: u64) -> Self {
+        Self {
+            pulls,
+            ten_pulls: false,
+            ..self
+        }
+    }
+
+    /// Makes each account do this many 10-pulls, respecting the Banner's
+    /// purchase limit
+    ///
+    /// # Arguments
+    /// * `ten_pulls` - The amount of 10-pulls per account
+    pub fn with_ten_pulls(self, ten_pulls: u64) -> Self {
+        Self {
+            pulls: ten_pulls,
+            ten_pulls: true,
+            ..self
+        }
+    }
+
+    /// Sets the amount of simulated accounts
+    pub fn with_accounts(self, accounts: u64) -> Self {
+        Self { accounts, ..self }
+    }
+
+    /// Sets the maximum amount of threads the accounts are split across
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the seed every account's random stream is derived from
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Runs the Simulation, returning the combined counts of every account
+    pub fn run(&self) -> Histogram
+    where
+        T: Sync,
+    {
+        let roller = self.banner.roller();
+        let (seed, pulls, ten_pulls, selection) =
+            (self.seed, self.pulls, self.ten_pulls, self.selection);
+        let threads = (self.threads as u64).clamp(1, self.accounts.max(1));
+        let chunk = (self.accounts + threads - 1) / threads;
+
+        let tally = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|i| {
+                    let accounts =
+                        (i * chunk).min(self.accounts)..((i + 1) * chunk).min(self.accounts);
+                    scope.spawn(move || {
+                        run_accounts(roller, seed, pulls, ten_pulls, selection, accounts)
+                    })
+                })
+                .collect();
+
+            let mut tally = Tally::new();
+            for handle in handles {
+                tally.merge(handle.join().expect("a simulation thread panicked"));
+            }
+            tally
+        });
+
+        tally.into_histogram()
+    }
+}
+
+/// Pulls on behalf of a range of accounts, each with its own random stream
+fn run_accounts<'a, T: Item>(
+    roller: Roller<'a, T>,
+    seed: u64,
+    pulls: u64,
+    ten_pulls: bool,
+    selection: Option<&'a [T]>,
+    accounts: Range<u64>,
+) -> Tally<'a, T> {
+    let mut tally = Tally::new();
+
+    for account in accounts {
+        let mut rng = rng::seeded(seed);
+        rng.set_stream(account);
+
+        let mut state = PityState::default();
+
+        if let Some(choices) = selection {
+            if let Some(mut students) = roller.ten_pull(&mut state, 0, &mut rng) {
+                students[students.len() - 1] = &choices[rng.gen_range(0..choices.len())];
+                students.iter().for_each(|&student| tally.add(student, 1));
+            }
+        } else if ten_pulls {
+            for purchases in 0..pulls {
+                let purchases = u32::try_from(purchases).unwrap_or(u32::MAX);
+                match roller.ten_pull(&mut state, purchases, &mut rng) {
+                    Some(students) => students.iter().for_each(|&student| tally.add(student, 1)),
+                    None => break,
+                }
+            }
+        } else if roller.rules.independent_pulls() {
+            roller
+                .sampler
+                .sample_counts(pulls, &mut rng, |student, copies| {
+                    tally.add(student, copies)
+                });
+        } else {
+            for _ in 0..pulls {
+                tally.add(roller.pull(&mut state, &mut rng), 1);
+            }
+        }
+    }
+
+    tally
+}
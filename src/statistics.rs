@@ -0,0 +1,213 @@
+//! Goodness-of-fit tests, for checking pulled counts against a Banner's rates
+
+/// The result of a goodness-of-fit test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoodnessOfFit {
+    /// The test statistic, which is chi-square distributed if the counts
+    /// follow the expected probabilities
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    /// The probability of a statistic at least this large if the counts
+    /// follow the expected probabilities
+    pub p_value: f64,
+}
+
+impl GoodnessOfFit {
+    /// Returns true if the counts are consistent with the expected
+    /// probabilities at the given significance level
+    ///
+    /// # Arguments
+    /// * `significance` - The chance of wrongly rejecting correct probabilities, such as 0.001
+    pub fn is_consistent(&self, significance: f64) -> bool {
+        self.p_value >= significance
+    }
+}
+
+/// Performs Pearson's chi-square test
+///
+/// Categories with a probability of 0 must have a count of 0, otherwise the
+/// p-value is 0. They don't count towards the degrees of freedom.
+///
+/// Will return `None` if `observed` and `expected` have different lengths, or
+/// if nothing was observed.
+///
+/// # Arguments
+/// * `observed` - How many times each category was observed
+/// * `expected` - The probability of each category, which should add up to 1
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::statistics::chi_square;
+/// let fit = chi_square(&[7890, 1870, 240], &[0.79, 0.185, 0.025]).unwrap();
+/// assert!(fit.is_consistent(0.001));
+///
+/// let fit = chi_square(&[7000, 2500, 500], &[0.79, 0.185, 0.025]).unwrap();
+/// assert!(!fit.is_consistent(0.001));
+/// ```
+pub fn chi_square(observed: &[u64], expected: &[f64]) -> Option<GoodnessOfFit> {
+    goodness_of_fit(observed, expected, |observed, expected| {
+        (observed - expected).powi(2) / expected
+    })
+}
+
+/// Performs a G-test (likelihood-ratio test)
+///
+/// See [`chi_square`] for how the arguments are handled.
+///
+/// # Arguments
+/// * `observed` - How many times each category was observed
+/// * `expected` - The probability of each category, which should add up to 1
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::statistics::g_test;
+/// let fit = g_test(&[7890, 1870, 240], &[0.79, 0.185, 0.025]).unwrap();
+/// assert!(fit.is_consistent(0.001));
+/// ```
+pub fn g_test(observed: &[u64], expected: &[f64]) -> Option<GoodnessOfFit> {
+    goodness_of_fit(observed, expected, |observed, expected| {
+        if observed == 0.0 {
+            0.0
+        } else {
+            2.0 * observed * (observed / expected).ln()
+        }
+    })
+}
+
+fn goodness_of_fit<F>(observed: &[u64], expected: &[f64], term: F) -> Option<GoodnessOfFit>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    if observed.len() != expected.len() {
+        return None;
+    }
+
+    let total: u64 = observed.iter().sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut statistic = 0.0;
+    let mut categories = 0;
+
+    for (&observed, &probability) in observed.iter().zip(expected) {
+        if probability <= 0.0 {
+            if observed > 0 {
+                statistic = f64::INFINITY;
+            }
+            continue;
+        }
+
+        categories += 1;
+        statistic += term(observed as f64, probability * total as f64);
+    }
+
+    let degrees_of_freedom = categories.max(1) - 1;
+
+    Some(GoodnessOfFit {
+        statistic,
+        degrees_of_freedom,
+        p_value: chi_square_p_value(statistic, degrees_of_freedom),
+    })
+}
+
+/// Returns the probability of a chi-square distributed value being at least
+/// `statistic`
+///
+/// # Arguments
+/// * `statistic` - The test statistic
+/// * `degrees_of_freedom` - The degrees of freedom of the chi-square distribution
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::statistics::chi_square_p_value;
+/// assert!((chi_square_p_value(3.841, 1) - 0.05).abs() < 1e-4);
+/// ```
+pub fn chi_square_p_value(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    if statistic.is_infinite() {
+        0.0
+    } else if degrees_of_freedom == 0 || statistic <= 0.0 {
+        1.0
+    } else {
+        upper_regularized_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+    }
+}
+
+const EPSILON: f64 = 1e-15;
+const MAX_ITERATIONS: usize = 10_000;
+
+/// Q(a, x), using a series when x is small and a continued fraction otherwise
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    let prefix = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..MAX_ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+
+        (1.0 - prefix * sum).max(0.0)
+    } else {
+        // Modified Lentz's method
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+
+        for n in 1..MAX_ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+
+        (prefix * h).min(1.0)
+    }
+}
+
+/// ln Γ(x) for x > 0, using the Lanczos approximation
+fn ln_gamma(x: f64) -> f64 {
+    #[rustfmt::skip]
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9, 676.520_368_121_885_1, -1_259.139_216_722_402_8,
+        771.323_428_777_653_1, -176.615_029_162_140_6, 12.507_343_278_686_905,
+        -0.138_571_095_265_720_12, 9.984_369_578_019_572e-6, 1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, &coefficient)| {
+            sum + coefficient / (x + i as f64 + 1.0)
+        });
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
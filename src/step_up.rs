@@ -0,0 +1,381 @@
+use crate::banner::Banner;
+use crate::gacha::{Gacha, Item, Rarity, Recruitment};
+use crate::i18n::I18nString;
+use crate::pity::PityState;
+use crate::sampler::Sampler;
+use crate::student::Student;
+use rand::RngCore;
+use std::cell::Cell;
+
+/// A single step of a Step-Up Recruitment, which is always a 10-pull
+#[derive(Debug, Clone, Default)]
+pub struct Step {
+    /// The amount of Pyroxene this step costs
+    pub cost: u32,
+    /// The percent chance of pulling each of the Banner's Tiers, from the
+    /// most common to the rarest, if it differs from the Banner's
+    pub rates: Option<Vec<f32>>,
+    /// The minimum Rarity of at least one Student pulled in this step
+    pub guarantee: Option<Rarity>,
+}
+
+impl Step {
+    /// Creates a new Step
+    ///
+    /// # Arguments
+    /// * `cost` - The amount of Pyroxene this step costs
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::step_up::Step;
+    /// let discounted = Step::new(600);
+    /// let last = Step::new(1200).with_guarantee(Rarity::Three);
+    /// ```
+    pub fn new(cost: u32) -> Self {
+        Self {
+            cost,
+            ..Default::default()
+        }
+    }
+
+    /// Overrides the Banner's rates for this step
+    ///
+    /// # Arguments
+    /// * `rates` - The percent chance of pulling each of the Banner's Tiers,
+    ///   from the most common to the rarest
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::step_up::Step;
+    /// let step = Step::new(1200).with_rates(vec![76.0, 18.5, 5.5]);
+    /// ```
+    pub fn with_rates(self, rates: Vec<f32>) -> Self {
+        Self {
+            rates: Some(rates),
+            ..self
+        }
+    }
+
+    /// Guarantees that at least one Student of this Rarity or higher is
+    /// pulled in this step
+    ///
+    /// # Arguments
+    /// * `rarity` - The guaranteed Rarity
+    pub fn with_guarantee(self, rarity: Rarity) -> Self {
+        Self {
+            guarantee: Some(rarity),
+            ..self
+        }
+    }
+}
+
+/// Used to Construct a StepUpBanner
+#[derive(Debug)]
+pub struct StepUpBuilder {
+    banner: Banner,
+    steps: Vec<Step>,
+    looping: bool,
+}
+
+impl StepUpBuilder {
+    /// Creates a new instance of a StepUpBuilder
+    ///
+    /// # Arguments
+    /// * `banner` - The Banner every step pulls from
+    pub fn new(banner: Banner) -> Self {
+        Self {
+            banner,
+            steps: Vec::new(),
+            looping: false,
+        }
+    }
+
+    /// Adds a step, after every step which has already been added
+    ///
+    /// # Arguments
+    /// * `step` - The step to add
+    pub fn with_step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Whether the Banner starts again from the first step after the last one
+    ///
+    /// # Arguments
+    /// * `looping` - If false, the Banner ends after the last step
+    pub fn looping(self, looping: bool) -> Self {
+        Self { looping, ..self }
+    }
+
+    /// Consumes a StepUpBuilder and returns a StepUpBanner
+    ///
+    /// Will return `None` if no steps have been added, if a step's rates
+    /// don't match the Banner's Tiers or leave a pull without a Student to
+    /// give (see [`Gacha::validate`]), or if a step guarantees a Rarity which
+    /// no Student in the pool has.
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::banner::BannerBuilder;
+    /// # use blue_gacha::gacha::{GachaBuilder, Rarity, Recruitment};
+    /// # use blue_gacha::step_up::{Step, StepUpBuilder};
+    /// # use blue_gacha::student::Student;
+    /// let pool = vec![
+    ///     Student::new("ホシノ", Rarity::Three),
+    ///     Student::new("セリカ", Rarity::Two),
+    ///     Student::new("ハルカ", Rarity::One),
+    /// ];
+    /// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+    /// let banner = BannerBuilder::new("ステップアップ募集").with_gacha(gacha).finish().unwrap();
+    ///
+    /// // The Banner has three Tiers
+    /// let invalid = StepUpBuilder::new(banner.clone()).with_step(Step::new(600).with_rates(vec![80.0, 20.0]));
+    /// assert!(invalid.finish().is_none());
+    ///
+    /// let step_up = StepUpBuilder::new(banner)
+    ///     .with_step(Step::new(600))
+    ///     .with_step(Step::new(1200).with_rates(vec![76.0, 18.5, 5.5]))
+    ///     .with_step(Step::new(1200).with_guarantee(Rarity::Three))
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(step_up.current_cost(), Some(600));
+    /// step_up.roll10();
+    /// step_up.roll10();
+    /// let students = step_up.roll10();
+    ///
+    /// assert!(students.iter().any(|student| student.rarity == Rarity::Three));
+    /// assert!(step_up.is_finished());
+    /// ```
+    pub fn finish(self) -> Option<StepUpBanner> {
+        if self.steps.is_empty() {
+            return None;
+        }
+
+        let pool = &self.banner.gacha().pool;
+        let guaranteed_in_pool = self
+            .steps
+            .iter()
+            .filter_map(|step| step.guarantee)
+            .all(|rarity| pool.iter().any(|student| student.rarity() == rarity));
+        if !guaranteed_in_pool {
+            return None;
+        }
+
+        let samplers = self
+            .steps
+            .iter()
+            .map(|step| {
+                let gacha = match &step.rates {
+                    Some(rates) => Gacha {
+                        tiers: self.banner.gacha().tiers.with_rates(rates)?,
+                        ..self.banner.gacha().clone()
+                    },
+                    None => self.banner.gacha().clone(),
+                };
+                gacha.validate().ok()?;
+
+                Some(Sampler::new(gacha))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(StepUpBanner {
+            steps: self.steps,
+            banner: self.banner,
+            samplers,
+            looping: self.looping,
+            current: Cell::new(0),
+            pity_state: Default::default(),
+        })
+    }
+}
+
+/// A Banner whose 10-pulls go through a series of steps, each with its own
+/// cost, rates and guarantees
+///
+/// The current step advances after every 10-pull. Single pulls use the
+/// current step's rates, but don't advance it. Every step counts towards the
+/// Banner's purchase limit, if it has one.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::banner::BannerBuilder;
+/// # use blue_gacha::gacha::{GachaBuilder, Rarity, Recruitment};
+/// # use blue_gacha::step_up::{Step, StepUpBuilder};
+/// # use blue_gacha::student::Student;
+/// let pool = vec![
+///     Student::new("ホシノ", Rarity::Three),
+///     Student::new("セリカ", Rarity::Two),
+///     Student::new("ハルカ", Rarity::One),
+/// ];
+/// let gacha = GachaBuilder::default().with_pool(pool).finish().unwrap();
+/// let banner = BannerBuilder::new("ステップアップ募集")
+///     .with_gacha(gacha)
+///     .with_purchase_limit(2)
+///     .finish()
+///     .unwrap();
+///
+/// let step_up = StepUpBuilder::new(banner)
+///     .with_step(Step::new(600))
+///     .with_step(Step::new(1200))
+///     .looping(true)
+///     .finish()
+///     .unwrap();
+///
+/// assert!(step_up.try_roll10().is_some());
+/// assert!(step_up.try_roll10().is_some());
+/// assert!(step_up.try_roll10().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StepUpBanner {
+    steps: Vec<Step>,
+    /// The Banner every step pulls from, which keeps track of purchases
+    banner: Banner,
+    /// The Sampler used for each step, with the step's rates applied
+    samplers: Vec<Sampler>,
+    looping: bool,
+    current: Cell<usize>,
+    /// Pity progress of pulls made directly on this Banner, rather than through a Session
+    pity_state: Cell<PityState>,
+}
+
+impl StepUpBanner {
+    /// Returns the index of the current step, or `None` if the Banner has ended
+    pub fn current_step(&self) -> Option<usize> {
+        let current = self.current.get();
+        (current < self.steps.len()).then_some(current)
+    }
+
+    /// Returns the cost of the current step, or `None` if the Banner has ended
+    pub fn current_cost(&self) -> Option<u32> {
+        self.current_step().map(|i| self.steps[i].cost)
+    }
+
+    /// Returns every step of this Banner
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Returns true if the last step has been pulled, and the Banner doesn't loop
+    pub fn is_finished(&self) -> bool {
+        self.current_step().is_none()
+    }
+
+    /// Moves back to the first step
+    pub fn reset(&self) {
+        self.current.set(0);
+    }
+
+    /// Performs the current step's 10-pull, and advances to the next step
+    ///
+    /// Will return `None` if the Banner has ended, or its purchase limit has
+    /// been reached.
+    pub fn try_roll10(&self) -> Option<[Student; 10]> {
+        let mut state = self.pity_state.get();
+        let students = self.try_roll10_with_pity(&mut state);
+        self.pity_state.set(state);
+        students
+    }
+
+    /// Performs the current step's 10-pull progressing towards pity, see
+    /// [`StepUpBanner::try_roll10`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    pub fn try_roll10_with_pity(&self, state: &mut PityState) -> Option<[Student; 10]> {
+        self.try_roll10_with_rng(state, &mut rand::thread_rng())
+    }
+
+    /// Performs the current step's 10-pull using the given source of
+    /// randomness, see [`StepUpBanner::try_roll10`]
+    ///
+    /// # Arguments
+    /// * `state` - The player's progress towards pity
+    /// * `rng` - The source of randomness
+    pub fn try_roll10_with_rng(
+        &self,
+        state: &mut PityState,
+        rng: &mut dyn RngCore,
+    ) -> Option<[Student; 10]> {
+        let i = self.current_step()?;
+        let sampler = &self.samplers[i];
+
+        let mut students = self.banner.try_roll10_ref_from(sampler, state, rng)?;
+        if let Some(rarity) = self.steps[i].guarantee {
+            self.banner
+                .roller_with(sampler)
+                .guarantee_rarity(&mut students, rarity, rng);
+        }
+
+        let next = i + 1;
+        self.current
+            .set(if self.looping && next == self.steps.len() {
+                0
+            } else {
+                next
+            });
+
+        Some(students.map(Student::clone))
+    }
+}
+
+impl Recruitment for StepUpBanner {
+    fn name(&self) -> &I18nString {
+        &self.banner.name
+    }
+
+    fn spark_banner(&self) -> Option<&Banner> {
+        Some(&self.banner)
+    }
+
+    /// Performs a single pull using the current step's rates
+    ///
+    /// # Panics
+    /// Panics if the Banner has ended
+    fn roll(&self) -> Student {
+        let mut state = self.pity_state.get();
+        let student = self.roll_with_pity(&mut state);
+        self.pity_state.set(state);
+        student
+    }
+
+    /// Performs the current step's 10-pull, see [`StepUpBanner::try_roll10`]
+    ///
+    /// # Panics
+    /// Panics if the Banner has ended
+    fn roll10(&self) -> [Student; 10] {
+        self.try_roll10().expect("step-up banner has ended")
+    }
+
+    fn roll_with_pity(&self, state: &mut PityState) -> Student {
+        let i = self.current_step().expect("step-up banner has ended");
+        self.banner
+            .roller_with(&self.samplers[i])
+            .pull(state, &mut rand::thread_rng())
+            .clone()
+    }
+
+    /// Performs the current step's 10-pull progressing towards pity, see
+    /// [`StepUpBanner::try_roll10_with_pity`]
+    ///
+    /// # Panics
+    /// Panics if the Banner has ended
+    fn roll10_with_pity(&self, state: &mut PityState) -> [Student; 10] {
+        self.try_roll10_with_pity(state)
+            .expect("step-up banner has ended")
+    }
+
+    fn try_roll10(&self) -> Option<[Student; 10]> {
+        StepUpBanner::try_roll10(self)
+    }
+
+    /// Returns the cost of the current step, or 0 if the Banner has ended
+    fn ten_pull_cost(&self) -> u32 {
+        self.current_cost().unwrap_or_default()
+    }
+
+    fn try_roll10_with_pity(&self, state: &mut PityState) -> Option<[Student; 10]> {
+        StepUpBanner::try_roll10_with_pity(self, state)
+    }
+}
@@ -1,5 +1,6 @@
-use crate::gacha::Rarity;
-use crate::i18n::{I18nString, Language};
+use crate::gacha::{Item, Rarity};
+use crate::i18n::{I18nString, Language, Localize};
+use crate::tier::per_mille;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -8,11 +9,98 @@ pub struct Student {
     /// There is guaranteed to be a Japanese Name available at all times
     pub name: I18nString,
     pub rarity: Rarity,
+    /// The Japanese name of the Student this is an alternate version of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<Variant>,
+    /// Limited Students can only be pulled while they have increased rates,
+    /// unlike permanent Students who are always in the pool
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub limited: bool,
+}
+
+/// The costume or event an alternate version of a Student belongs to
+///
+/// In Blue Archive, alternate versions (e.g. ホシノ（水着）) are separate units
+/// in the gacha, who share a base character.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    NewYear,
+    Swimsuit,
+    Bunny,
+    Cheerleader,
+    Christmas,
+    HotSpring,
+    Other(String),
+}
+
+impl std::fmt::Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_localized(Language::Japanese, f)
+    }
+}
+
+impl Localize for Variant {
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let name = match (self, language) {
+            (Variant::NewYear, Language::Japanese) => "正月",
+            (Variant::NewYear, Language::English) => "New Year",
+            (Variant::Swimsuit, Language::Japanese) => "水着",
+            (Variant::Swimsuit, Language::English) => "Swimsuit",
+            (Variant::Bunny, Language::Japanese) => "バニーガール",
+            (Variant::Bunny, Language::English) => "Bunny Girl",
+            (Variant::Cheerleader, Language::Japanese) => "応援団",
+            (Variant::Cheerleader, Language::English) => "Cheer Squad",
+            (Variant::Christmas, Language::Japanese) => "クリスマス",
+            (Variant::Christmas, Language::English) => "Christmas",
+            (Variant::HotSpring, Language::Japanese) => "温泉",
+            (Variant::HotSpring, Language::English) => "Hot Spring",
+            (Variant::Other(name), _) => name.as_str(),
+        };
+
+        f.write_str(name)
+    }
 }
 
 impl std::fmt::Display for Student {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}\t{}", self.name, self.rarity)
+        self.fmt_localized(Language::Japanese, f)
+    }
+}
+
+impl Localize for Student {
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}",
+            self.name.display_in(language),
+            self.rarity.display_in(language)
+        )
+    }
+}
+
+impl Item for Student {
+    /// Students are identified by their Japanese name
+    fn id(&self) -> &str {
+        self.name.translation(Language::Japanese).unwrap()
+    }
+
+    fn rarity(&self) -> Rarity {
+        self.rarity
+    }
+
+    fn is_limited(&self) -> bool {
+        self.limited
     }
 }
 
@@ -34,6 +122,7 @@ impl Student {
         Self {
             name: I18nString::new(jpn_name),
             rarity,
+            ..Default::default()
         }
     }
 
@@ -56,31 +145,85 @@ impl Student {
         self.name.update(language, name);
     }
 
+    /// Marks this Student as an alternate version of another Student
+    ///
+    /// # Arguments
+    /// * `base_jpn_name` - The Japanese name of the base character
+    /// * `variant` - The costume or event this version belongs to
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::student::{Student, Variant};
+    /// let hoshino = Student::new("ホシノ", Rarity::Three);
+    /// let mut swimsuit_hoshino = Student::new("ホシノ（水着）", Rarity::Three);
+    /// swimsuit_hoshino.set_variant("ホシノ", Variant::Swimsuit);
+    ///
+    /// assert!(swimsuit_hoshino.is_version_of("ホシノ"));
+    /// assert!(swimsuit_hoshino.same_character(&hoshino));
+    /// ```
+    pub fn set_variant(&mut self, base_jpn_name: &str, variant: Variant) {
+        self.base = Some(base_jpn_name.to_string());
+        self.variant = Some(variant);
+    }
+
+    /// Returns the Japanese name of the base character. For Students which
+    /// aren't alternate versions, this is their own name, or an empty string
+    /// if they have no Japanese name.
+    pub fn base_name(&self) -> &str {
+        match &self.base {
+            Some(base) => base.as_str(),
+            None => self
+                .name
+                .translation(Language::Japanese)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns true if this Student is any version of the character with
+    /// the given Japanese name, including the base character themselves
+    ///
+    /// # Arguments
+    /// * `base_jpn_name` - The Japanese name of the base character
+    pub fn is_version_of(&self, base_jpn_name: &str) -> bool {
+        self.base_name() == base_jpn_name
+    }
+
+    /// Returns true if both Students are versions of the same character
+    ///
+    /// # Arguments
+    /// * `other` - The Student to compare against
+    pub fn same_character(&self, other: &Student) -> bool {
+        self.base_name() == other.base_name()
+    }
+
     pub fn into_priority_student(self, rate: f32) -> PriorityStudent {
         PriorityStudent {
             inner: self,
-            rate: (rate * 10.0) as usize,
+            rate: per_mille(rate),
         }
     }
 }
 
 /// A Priority Student is a student who has a pull-rate that is unique from
 /// the rest of the rest of their peers in their star rating
+///
+/// Despite the name, any [`Item`] can be given an increased rate.
 #[derive(Debug, Clone)]
-pub struct PriorityStudent {
-    inner: Student,
+pub struct PriorityStudent<T = Student> {
+    inner: T,
     pub rate: usize,
 }
 
-impl PriorityStudent {
-    pub fn new(student: Student, rate: f32) -> Self {
+impl<T: Item> PriorityStudent<T> {
+    pub fn new(student: T, rate: f32) -> Self {
         Self {
             inner: student,
-            rate: (rate * 10.0) as usize,
+            rate: per_mille(rate),
         }
     }
 
-    pub fn student(&self) -> &Student {
+    pub fn student(&self) -> &T {
         &self.inner
     }
 }
@@ -0,0 +1,215 @@
+use crate::gacha::Rarity;
+use crate::i18n::{I18nString, Language, Localize};
+
+const THREE_STAR_RATE: usize = 25;
+const TWO_STAR_RATE: usize = 185;
+const ONE_STAR_RATE: usize = 790;
+
+/// Converts a percent chance into a chance in terms of 1000, rounding to the
+/// nearest whole number so that e.g. 64.3% isn't truncated to 64.2%
+pub(crate) fn per_mille(percent: f32) -> usize {
+    (percent * 10.0).round() as usize
+}
+
+/// A single tier of a gacha's rarity system, such as 3★ or SSR
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tier {
+    pub name: I18nString,
+    /// How the tier is shown next to an Item, such as "★★★" or "SSR"
+    pub symbol: String,
+    /// The chance of pulling this tier (in terms of 1000)
+    pub rate: usize,
+}
+
+impl Tier {
+    /// Creates a new Tier
+    ///
+    /// # Arguments
+    /// * `jpn_name` - The name of the Tier in Japanese
+    /// * `symbol` - How the Tier is shown next to an Item
+    /// * `rate` - The percent chance of pulling this Tier
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::tier::Tier;
+    /// let ssr = Tier::new("SSR", "SSR", 3.0);
+    /// assert_eq!(ssr.rate, 30);
+    /// ```
+    pub fn new(jpn_name: &str, symbol: &str, rate: f32) -> Self {
+        Self {
+            name: I18nString::new(jpn_name),
+            symbol: symbol.to_string(),
+            rate: per_mille(rate),
+        }
+    }
+
+    /// Adds a translation to the name of the Tier
+    ///
+    /// # Arguments
+    /// * `language` - The language of the translation
+    /// * `name` - The translated name
+    pub fn with_name_translation(mut self, language: Language, name: &str) -> Self {
+        self.name.update(language, name);
+        self
+    }
+}
+
+impl std::fmt::Display for Tier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+impl Localize for Tier {
+    fn fmt_localized(
+        &self,
+        language: Language,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.name.fmt_localized(language, f)
+    }
+}
+
+/// An ordered set of Tiers, from the most common to the rarest
+///
+/// The first Tier is [`Rarity`] 1, the second is Rarity 2, and so on.
+///
+/// # Examples
+/// ```
+/// # use blue_gacha::gacha::Rarity;
+/// # use blue_gacha::tier::{Tier, TierSet};
+/// let tiers = TierSet::new(vec![
+///     Tier::new("N", "N", 60.0),
+///     Tier::new("R", "R", 30.0),
+///     Tier::new("SR", "SR", 8.5),
+///     Tier::new("SSR", "SSR", 1.2),
+///     Tier::new("UR", "UR", 0.3),
+/// ])
+/// .unwrap();
+///
+/// assert_eq!(tiers.top(), Rarity(5));
+/// assert_eq!(tiers.get(Rarity(4)).unwrap().symbol, "SSR");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierSet {
+    tiers: Vec<Tier>,
+}
+
+impl Default for TierSet {
+    fn default() -> Self {
+        Self::blue_archive()
+    }
+}
+
+impl TierSet {
+    /// Creates a new TierSet
+    ///
+    /// Will return `None` if there are no Tiers, more Tiers than a
+    /// [`Rarity`] can represent, or if the rates do not add up to 100%.
+    ///
+    /// # Arguments
+    /// * `tiers` - Every Tier, from the most common to the rarest
+    pub fn new(tiers: Vec<Tier>) -> Option<Self> {
+        if tiers.is_empty() || tiers.len() > u8::MAX as usize {
+            return None;
+        }
+
+        if tiers.iter().map(|tier| tier.rate).sum::<usize>() != 1000 {
+            return None;
+        }
+
+        Some(Self { tiers })
+    }
+
+    /// The 1★ to 3★ Tiers of Blue Archive
+    ///
+    /// * 1★: 79.0%
+    /// * 2★: 18.5%
+    /// * 3★: 2.5%
+    pub fn blue_archive() -> Self {
+        Self::blue_archive_with_rates(ONE_STAR_RATE, TWO_STAR_RATE, THREE_STAR_RATE)
+    }
+
+    /// The 1★ to 3★ Tiers of Blue Archive with custom rates (in terms of 1000)
+    pub(crate) fn blue_archive_with_rates(one: usize, two: usize, three: usize) -> Self {
+        let star = |count: usize, rate: usize| {
+            Tier {
+                name: I18nString::new(&format!("{}★", count)),
+                symbol: "★".repeat(count),
+                rate,
+            }
+            .with_name_translation(Language::English, &format!("{}-star", count))
+        };
+
+        Self {
+            tiers: vec![star(1, one), star(2, two), star(3, three)],
+        }
+    }
+
+    /// Returns a copy of these Tiers with different rates
+    ///
+    /// Will return `None` if there isn't exactly one rate per Tier, or if the
+    /// rates do not add up to 100%.
+    ///
+    /// # Arguments
+    /// * `rates` - The percent chance of pulling each Tier, from the most common to the rarest
+    ///
+    /// # Examples
+    /// ```
+    /// # use blue_gacha::gacha::Rarity;
+    /// # use blue_gacha::tier::TierSet;
+    /// let tiers = TierSet::blue_archive().with_rates(&[64.3, 33.2, 2.5]).unwrap();
+    /// assert_eq!(tiers.rate(Rarity::One), 643);
+    ///
+    /// assert!(TierSet::blue_archive().with_rates(&[79.0, 21.0]).is_none());
+    /// ```
+    pub fn with_rates(&self, rates: &[f32]) -> Option<Self> {
+        if rates.len() != self.tiers.len() {
+            return None;
+        }
+
+        let tiers = self
+            .tiers
+            .iter()
+            .zip(rates)
+            .map(|(tier, &rate)| Tier {
+                rate: per_mille(rate),
+                ..tier.clone()
+            })
+            .collect();
+
+        Self::new(tiers)
+    }
+
+    /// Returns every Tier, from the most common to the rarest
+    pub fn tiers(&self) -> &[Tier] {
+        &self.tiers
+    }
+
+    /// Returns the Tier of the given Rarity
+    ///
+    /// # Arguments
+    /// * `rarity` - The Rarity of the Tier
+    pub fn get(&self, rarity: Rarity) -> Option<&Tier> {
+        (rarity.0 as usize)
+            .checked_sub(1)
+            .and_then(|index| self.tiers.get(index))
+    }
+
+    /// Returns every Rarity in this TierSet, from the most common to the rarest
+    pub fn rarities(&self) -> impl Iterator<Item = Rarity> {
+        (1..=self.tiers.len() as u8).map(Rarity)
+    }
+
+    /// Returns the Rarity of the rarest Tier
+    pub fn top(&self) -> Rarity {
+        Rarity(self.tiers.len() as u8)
+    }
+
+    /// Returns the chance of pulling the given Rarity (in terms of 1000)
+    ///
+    /// Rarities outside of this TierSet have a rate of 0.
+    pub fn rate(&self, rarity: Rarity) -> usize {
+        self.get(rarity).map_or(0, |tier| tier.rate)
+    }
+}
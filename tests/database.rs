@@ -0,0 +1,60 @@
+//! Loading Student databases and Banner definitions from untrusted JSON
+use blue_gacha::database::StudentDatabase;
+use blue_gacha::definition::BannerDefinition;
+use blue_gacha::error::Error;
+use blue_gacha::i18n::Language;
+
+#[test]
+fn audit_reports_students_without_a_japanese_name() {
+    let json = r#"[
+        { "name": { "translations": { "jpn": "アル", "eng": "Aru" } }, "rarity": 3 },
+        { "name": { "translations": { "eng": "Mutsuki" } }, "rarity": 2 },
+        { "name": { "translations": {} }, "rarity": 1 }
+    ]"#;
+    let audit = StudentDatabase::from_json(json).unwrap().audit();
+
+    assert_eq!(
+        audit.missing[&Language::Japanese],
+        vec!["Mutsuki".to_string(), "#2".to_string()]
+    );
+    assert_eq!(audit.missing[&Language::English], vec!["#2".to_string()]);
+}
+
+#[test]
+fn strict_loading_rejects_students_without_a_japanese_name() {
+    let json = r#"[{ "name": { "translations": { "eng": "Mutsuki" } }, "rarity": 2 }]"#;
+
+    match StudentDatabase::from_json_strict(json) {
+        Err(Error::IncompleteDatabase(audit)) => {
+            assert_eq!(
+                audit.missing[&Language::Japanese],
+                vec!["Mutsuki".to_string()]
+            )
+        }
+        other => panic!(
+            "expected an incomplete database, got {:?}",
+            other.map(|_| ())
+        ),
+    }
+}
+
+#[test]
+fn definitions_without_japanese_names_are_rejected() {
+    let db = StudentDatabase::from_json(
+        r#"[
+            { "name": { "translations": { "jpn": "ホシノ" } }, "rarity": 3 },
+            { "name": { "translations": { "eng": "Serika" } }, "rarity": 2 },
+            { "name": { "translations": { "jpn": "ハルカ" } }, "rarity": 1 }
+        ]"#,
+    )
+    .unwrap();
+
+    let unnamed: BannerDefinition =
+        serde_json::from_str(r#"{ "name": { "translations": { "eng": "Recruitment" } } }"#)
+            .unwrap();
+    assert!(matches!(unnamed.build(&db), Err(Error::InvalidBanner(_))));
+
+    let named: BannerDefinition =
+        serde_json::from_str(r#"{ "name": { "translations": { "jpn": "通常募集" } } }"#).unwrap();
+    assert!(matches!(named.build(&db), Err(Error::InvalidBanner(_))));
+}
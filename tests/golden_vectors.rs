@@ -0,0 +1,149 @@
+//! Pulls which a given seed and Banner must keep producing across releases
+//!
+//! If any of these fail, the sampling procedure has changed: bump
+//! `rng::SAMPLING_VERSION` and regenerate the vectors in the same commit.
+use blue_gacha::banner::{Banner, BannerBuilder};
+use blue_gacha::fair::{FairBanner, ServerSeed};
+use blue_gacha::gacha::{GachaBuilder, Item, Rarity};
+use blue_gacha::pity::PityState;
+use blue_gacha::rng::{self, seeded, SAMPLING_VERSION};
+use blue_gacha::student::Student;
+use rand::RngCore;
+
+/// The version these vectors were generated with
+const GOLDEN_VERSION: u32 = 1;
+
+/// `roll10` from a fresh PityState, for each seed
+#[rustfmt::skip]
+const ROLL10: [(u64, [&str; 10]); 5] = [
+    (0, ["スズミ", "ヨシミ", "スズミ", "ヨシミ", "ヨシミ", "スズミ", "スズミ", "スズミ", "セリカ", "ヨシミ"]),
+    (1, ["ホシノ", "セリカ", "ヨシミ", "ヨシミ", "スズミ", "ハルカ", "ヨシミ", "セリカ", "ヨシミ", "ハルカ"]),
+    (7, ["ヨシミ", "ヨシミ", "セリカ", "ハルカ", "セリカ", "ムツキ", "ヨシミ", "ムツキ", "ヨシミ", "ハルカ"]),
+    (2021, ["ハルカ", "セリカ", "ホシノ", "ハルカ", "スズミ", "ヨシミ", "ハルカ", "ハルカ", "スズミ", "ヨシミ"]),
+    (u64::MAX, ["ヨシミ", "スズミ", "スズミ", "スズミ", "セリカ", "スズミ", "ハルカ", "セリカ", "ハルカ", "ハルカ"]),
+];
+
+/// Ten single pulls with seed 42
+#[rustfmt::skip]
+const ROLLS: [&str; 10] = ["ヨシミ", "ハルカ", "スズミ", "セリカ", "ヒナ", "セリカ", "スズミ", "スズミ", "ハルカ", "ハルカ"];
+
+/// The first 10-pull of a FairBanner with an all-zero seed and the nonce "golden"
+#[rustfmt::skip]
+const FAIR_ROLL10: [&str; 10] = ["ヨシミ", "ハルカ", "ハルカ", "スズミ", "ヨシミ", "ハルカ", "ヨシミ", "スズミ", "ヨシミ", "ムツキ"];
+
+fn banner() -> Banner {
+    let aru = Student::new("アル", Rarity::Three);
+    let mutsuki = Student::new("ムツキ", Rarity::Two);
+    let pool = vec![
+        aru.clone(),
+        Student::new("ヒナ", Rarity::Three),
+        Student::new("ホシノ", Rarity::Three),
+        mutsuki.clone(),
+        Student::new("セリカ", Rarity::Two),
+        Student::new("ハルカ", Rarity::One),
+        Student::new("ヨシミ", Rarity::One),
+        Student::new("スズミ", Rarity::One),
+    ];
+    let gacha = GachaBuilder::new(79.0, 18.5, 2.5)
+        .with_pool(pool)
+        .with_priority(vec![
+            aru.into_priority_student(0.7),
+            mutsuki.into_priority_student(3.0),
+        ])
+        .finish()
+        .unwrap();
+
+    BannerBuilder::new("ピックアップ募集")
+        .with_gacha(gacha)
+        .finish()
+        .unwrap()
+}
+
+fn ids<'a>(students: impl IntoIterator<Item = &'a Student>) -> Vec<&'a str> {
+    students.into_iter().map(|student| student.id()).collect()
+}
+
+#[test]
+fn sampling_version_matches_vectors() {
+    assert_eq!(
+        SAMPLING_VERSION, GOLDEN_VERSION,
+        "regenerate the golden vectors for the new sampling version"
+    );
+    assert_eq!(rng::ALGORITHM, "ChaCha8");
+}
+
+#[test]
+fn seeded_rng_is_stable() {
+    let mut rng = seeded(0);
+    let outputs = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+
+    assert_eq!(
+        outputs,
+        [0xb585f767a79a3b6c, 0x7746a55fbad8c037, 0xb2fb0d3281e2a6e6]
+    );
+}
+
+#[test]
+fn roll10_is_stable() {
+    let banner = banner();
+    for (seed, expected) in ROLL10.iter() {
+        let students = banner
+            .try_roll10_ref_with_rng(&mut PityState::default(), &mut seeded(*seed))
+            .unwrap();
+
+        assert_eq!(ids(students.iter().copied()), expected, "seed {}", seed);
+    }
+}
+
+#[test]
+fn roll_is_stable() {
+    let banner = banner();
+    let mut rng = seeded(42);
+    let mut state = PityState::default();
+
+    let students: Vec<&Student> = (0..10)
+        .map(|_| banner.roll_ref_with_rng(&mut state, &mut rng))
+        .collect();
+
+    assert_eq!(ids(students), ROLLS);
+}
+
+#[test]
+fn simulation_is_stable() {
+    let banner = banner();
+
+    let histogram = banner
+        .simulate()
+        .with_ten_pulls(10)
+        .with_accounts(10)
+        .with_seed(7)
+        .with_threads(3)
+        .run();
+    assert_eq!(
+        histogram.rarities().collect::<Vec<_>>(),
+        [(Rarity::One, 781), (Rarity::Two, 195), (Rarity::Three, 24)]
+    );
+
+    let histogram = banner
+        .simulate()
+        .with_pulls(1000)
+        .with_accounts(10)
+        .with_seed(7)
+        .run();
+    assert_eq!(
+        histogram.rarities().collect::<Vec<_>>(),
+        [
+            (Rarity::One, 7865),
+            (Rarity::Two, 1876),
+            (Rarity::Three, 259)
+        ]
+    );
+}
+
+#[test]
+fn fair_roll10_is_stable() {
+    let banner = banner();
+    let mut fair = FairBanner::new(&banner, ServerSeed::new([0; 32]), "golden");
+
+    assert_eq!(ids(fair.try_roll10().unwrap().iter().copied()), FAIR_ROLL10);
+}
@@ -0,0 +1,259 @@
+use blue_gacha::banner::{Banner, BannerBuilder};
+use blue_gacha::gacha::{Gacha, GachaBuilder, Item, Rarity};
+use blue_gacha::pity::PityState;
+use blue_gacha::statistics::{chi_square, g_test};
+use blue_gacha::student::Student;
+use blue_gacha::tier::{Tier, TierSet};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+
+/// Correct rates fail at most once in 10,000 seeds
+const SIGNIFICANCE: f64 = 1e-4;
+const PULLS: usize = 200_000;
+const TEN_PULLS: usize = 20_000;
+
+fn gacha() -> Gacha {
+    let aru = Student::new("アル", Rarity::Three);
+    let mutsuki = Student::new("ムツキ", Rarity::Two);
+    let pool = vec![
+        aru.clone(),
+        Student::new("ヒナ", Rarity::Three),
+        Student::new("ホシノ", Rarity::Three),
+        mutsuki.clone(),
+        Student::new("セリカ", Rarity::Two),
+        Student::new("ハルカ", Rarity::One),
+        Student::new("ヨシミ", Rarity::One),
+        Student::new("スズミ", Rarity::One),
+    ];
+
+    GachaBuilder::new(79.0, 18.5, 2.5)
+        .with_pool(pool)
+        .with_priority(vec![
+            aru.into_priority_student(0.7),
+            mutsuki.into_priority_student(3.0),
+        ])
+        .finish()
+        .unwrap()
+}
+
+fn banner(gacha: Gacha) -> Banner {
+    BannerBuilder::new("ピックアップ募集")
+        .with_gacha(gacha)
+        .finish()
+        .unwrap()
+}
+
+fn count<'a>(students: impl Iterator<Item = &'a Student>) -> HashMap<&'a str, u64> {
+    let mut counts = HashMap::new();
+    for student in students {
+        *counts.entry(student.id()).or_default() += 1;
+    }
+    counts
+}
+
+/// Checks the observed counts against the expected probabilities with both tests
+fn assert_fits(observed: &[u64], expected: &[f64]) {
+    let sum: f64 = expected.iter().sum();
+    assert!(
+        (sum - 1.0).abs() < 1e-9,
+        "expected probabilities add up to {}",
+        sum
+    );
+
+    let chi = chi_square(observed, expected).unwrap();
+    let g = g_test(observed, expected).unwrap();
+
+    assert!(
+        chi.is_consistent(SIGNIFICANCE),
+        "{:?} {:?} {:?}",
+        chi,
+        observed,
+        expected
+    );
+    assert!(
+        g.is_consistent(SIGNIFICANCE),
+        "{:?} {:?} {:?}",
+        g,
+        observed,
+        expected
+    );
+}
+
+fn rarity_counts(counts: &HashMap<&str, u64>, gacha: &Gacha) -> Vec<u64> {
+    gacha
+        .tiers
+        .rarities()
+        .map(|rarity| {
+            gacha
+                .pool
+                .iter()
+                .filter(|student| student.rarity == rarity)
+                .map(|student| counts.get(student.id()).copied().unwrap_or_default())
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn roll_matches_rarity_rates() {
+    let gacha = gacha();
+    let banner = banner(gacha.clone());
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut state = PityState::default();
+
+    let counts = count((0..PULLS).map(|_| banner.roll_ref_with_rng(&mut state, &mut rng)));
+    let expected: Vec<f64> = gacha
+        .tiers
+        .rarities()
+        .map(|rarity| gacha.get_rate(rarity) as f64 / 1000.0)
+        .collect();
+
+    assert_fits(&rarity_counts(&counts, &gacha), &expected);
+}
+
+#[test]
+fn roll_matches_student_rates() {
+    let gacha = gacha();
+    let banner = banner(gacha.clone());
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    let mut state = PityState::default();
+
+    let counts = count((0..PULLS).map(|_| banner.roll_ref_with_rng(&mut state, &mut rng)));
+    let observed: Vec<u64> = gacha
+        .pool
+        .iter()
+        .map(|student| counts.get(student.id()).copied().unwrap_or_default())
+        .collect();
+    let expected: Vec<f64> = gacha
+        .pool
+        .iter()
+        .map(|student| gacha.probability_of(student))
+        .collect();
+
+    assert_fits(&observed, &expected);
+}
+
+#[test]
+fn roll10_matches_guaranteed_rates() {
+    let gacha = gacha();
+    let banner = banner(gacha.clone());
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    let mut state = PityState::default();
+
+    let counts = count((0..TEN_PULLS).flat_map(|_| {
+        banner
+            .try_roll10_ref_with_rng(&mut state, &mut rng)
+            .unwrap()
+    }));
+
+    // The last Student is replaced with a random 2★ if the 10-pull contains
+    // no 2★ Student, and the last Student isn't a 3★
+    let one = gacha.get_rate(Rarity::One) as f64 / 1000.0;
+    let two = gacha.get_rate(Rarity::Two) as f64 / 1000.0;
+    let replaced = (1.0 - two).powi(9) * one;
+    let two_stars = gacha
+        .pool
+        .iter()
+        .filter(|student| student.rarity == Rarity::Two)
+        .count() as f64;
+
+    let observed: Vec<u64> = gacha
+        .pool
+        .iter()
+        .map(|student| counts.get(student.id()).copied().unwrap_or_default())
+        .collect();
+    let expected: Vec<f64> = gacha
+        .pool
+        .iter()
+        .map(|student| {
+            let p = gacha.probability_of(student);
+            let last = match student.rarity {
+                Rarity::One => p * (1.0 - replaced / one),
+                Rarity::Two => p + replaced / two_stars,
+                _ => p,
+            };
+            (9.0 * p + last) / 10.0
+        })
+        .collect();
+
+    assert_fits(&observed, &expected);
+}
+
+#[test]
+fn sample_counts_matches_student_rates() {
+    let gacha = gacha();
+    let banner = banner(gacha.clone());
+
+    let histogram = banner
+        .simulate()
+        .with_pulls(PULLS as u64)
+        .with_accounts(10)
+        .with_seed(4)
+        .run();
+    let observed: Vec<u64> = gacha
+        .pool
+        .iter()
+        .map(|student| histogram.count(student.id()))
+        .collect();
+    let expected: Vec<f64> = gacha
+        .pool
+        .iter()
+        .map(|student| gacha.probability_of(student))
+        .collect();
+
+    assert_fits(&observed, &expected);
+}
+
+#[test]
+fn roll_matches_custom_tier_rates() {
+    let tiers = TierSet::new(vec![
+        Tier::new("N", "N", 60.0),
+        Tier::new("R", "R", 30.0),
+        Tier::new("SR", "SR", 8.5),
+        Tier::new("SSR", "SSR", 1.2),
+        Tier::new("UR", "UR", 0.3),
+    ])
+    .unwrap();
+    let pool: Vec<Student> = (1..=5)
+        .map(|rarity| Student::new(&format!("{}", rarity), Rarity(rarity)))
+        .collect();
+    let gacha = GachaBuilder::default()
+        .with_tiers(tiers)
+        .with_pool(pool)
+        .finish()
+        .unwrap();
+    let banner = banner(gacha.clone());
+    let mut rng = ChaCha8Rng::seed_from_u64(5);
+    let mut state = PityState::default();
+
+    let counts = count((0..PULLS).map(|_| banner.roll_ref_with_rng(&mut state, &mut rng)));
+    let expected: Vec<f64> = gacha
+        .tiers
+        .rarities()
+        .map(|rarity| gacha.get_rate(rarity) as f64 / 1000.0)
+        .collect();
+
+    assert_fits(&rarity_counts(&counts, &gacha), &expected);
+}
+
+#[test]
+fn wrong_rates_are_rejected() {
+    let gacha = gacha();
+    let banner = banner(gacha.clone());
+    let mut rng = ChaCha8Rng::seed_from_u64(6);
+    let mut state = PityState::default();
+
+    let counts = count((0..PULLS).map(|_| banner.roll_ref_with_rng(&mut state, &mut rng)));
+    let observed = rarity_counts(&counts, &gacha);
+
+    // 3★ at 3% rather than 2.5%
+    let expected = [0.785, 0.185, 0.03];
+
+    assert!(!chi_square(&observed, &expected)
+        .unwrap()
+        .is_consistent(SIGNIFICANCE));
+    assert!(!g_test(&observed, &expected)
+        .unwrap()
+        .is_consistent(SIGNIFICANCE));
+}